@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Loads annotations from a sidecar file and merges the inline annotations into them.
+///
+/// The sidecar file maps paths (relative to the visualized root) to descriptions and may be
+/// written in YAML, JSON or TOML. The format is picked by the file extension. Inline annotations
+/// take precedence over the ones from the file.
+pub fn load_annotations(
+    file: Option<PathBuf>,
+    inline: Vec<(PathBuf, String)>,
+) -> Result<HashMap<PathBuf, String>, String> {
+    let mut annotations = HashMap::new();
+
+    if let Some(file) = file {
+        for (path, text) in parse_annotations_file(&file)? {
+            annotations.insert(normalize_path(Path::new(&path)), text);
+        }
+    }
+
+    for (path, text) in inline {
+        annotations.insert(normalize_path(&path), text);
+    }
+
+    Ok(annotations)
+}
+
+/// Parse an inline annotation of the form `path=text`
+pub fn parse_inline_annotation(s: &str) -> Result<(PathBuf, String), String> {
+    match s.split_once('=') {
+        Some((path, text)) if !path.is_empty() => Ok((PathBuf::from(path), text.to_string())),
+        _ => Err(format!("Invalid annotation '{}'. Expected 'path=text'.", s)),
+    }
+}
+
+fn parse_annotations_file(file: &Path) -> Result<HashMap<String, String>, String> {
+    let content = fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read '{}': {}", file.display(), e))?;
+    let extension = file
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "yaml" | "yml" => {
+            serde_yaml::from_str(&content).map_err(|e| format!("Failed to parse YAML: {}", e))
+        }
+        "json" => {
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))
+        }
+        "toml" => toml::from_str(&content).map_err(|e| format!("Failed to parse TOML: {}", e)),
+        _ => Err(format!(
+            "Unsupported annotations file '{}'. Use a .yaml, .yml, .json or .toml file.",
+            file.display()
+        )),
+    }
+}
+
+/// Drops `./` components and trailing slashes so paths compare equal to the walked ones
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unreadable_files_are_errors() {
+        let missing = std::env::temp_dir().join("glimmr-missing-annotations.yaml");
+        assert!(load_annotations(Some(missing), Vec::new()).is_err());
+    }
+
+    #[test]
+    fn inline_annotations_are_normalized() {
+        let inline = vec![parse_inline_annotation("./src/=sources").unwrap()];
+        let annotations = load_annotations(None, inline).unwrap();
+        assert_eq!(
+            annotations.get(Path::new("src")).map(String::as_str),
+            Some("sources")
+        );
+        assert!(parse_inline_annotation("=text").is_err());
+    }
+}
@@ -0,0 +1,39 @@
+use std::str::FromStr;
+
+/// What the area or angle of an entry is proportional to in layouts that scale entries
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AreaBy {
+    /// Number of files, so every file gets the same area
+    Files,
+    /// Size in bytes, measured like `--sizes`
+    Size,
+    /// Lines of code, counted like `--loc`
+    Loc,
+}
+
+impl FromStr for AreaBy {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "files" => Ok(AreaBy::Files),
+            "size" => Ok(AreaBy::Size),
+            "loc" => Ok(AreaBy::Loc),
+            _ => Err("Invalid area metric. Choose from 'files', 'size', 'loc'."),
+        }
+    }
+}
+
+impl std::fmt::Display for AreaBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AreaBy::Files => "files",
+                AreaBy::Size => "size",
+                AreaBy::Loc => "loc",
+            }
+        )
+    }
+}
@@ -0,0 +1,39 @@
+use std::str::FromStr;
+
+/// What the background of entries is colored by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorBy {
+    /// Number of commits that touched a path
+    Commits,
+    /// Number of added and removed lines in a path
+    Lines,
+    /// Date of the last commit that touched a path, or its modification time outside of git
+    Age,
+}
+
+impl FromStr for ColorBy {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "commits" => Ok(ColorBy::Commits),
+            "lines" => Ok(ColorBy::Lines),
+            "age" => Ok(ColorBy::Age),
+            _ => Err("Invalid color mode. Choose from 'commits', 'lines', 'age'."),
+        }
+    }
+}
+
+impl std::fmt::Display for ColorBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ColorBy::Commits => "commits",
+                ColorBy::Lines => "lines",
+                ColorBy::Age => "age",
+            }
+        )
+    }
+}
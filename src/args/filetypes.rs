@@ -1,9 +1,12 @@
 use std::str::FromStr;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone)]
 pub enum Filetype {
     SVG,
     PNG,
+    TXT,
+    JSON,
 }
 
 impl Filetype {
@@ -12,6 +15,8 @@ impl Filetype {
         match self {
             Filetype::SVG => "svg",
             Filetype::PNG => "png",
+            Filetype::TXT => "txt",
+            Filetype::JSON => "json",
         }
     }
 }
@@ -23,7 +28,9 @@ impl FromStr for Filetype {
         match s.to_lowercase().as_str() {
             "svg" => Ok(Filetype::SVG),
             "png" => Ok(Filetype::PNG),
-            _ => Err("Invalid Filetype. Choose from 'svg', 'png'."),
+            "txt" => Ok(Filetype::TXT),
+            "json" => Ok(Filetype::JSON),
+            _ => Err("Invalid Filetype. Choose from 'svg', 'png', 'txt', 'json'."),
         }
     }
 }
@@ -36,6 +43,8 @@ impl std::fmt::Display for Filetype {
             match self {
                 Filetype::SVG => "svg",
                 Filetype::PNG => "png",
+                Filetype::TXT => "txt",
+                Filetype::JSON => "json",
             }
         )
     }
@@ -0,0 +1,61 @@
+use std::str::FromStr;
+
+/// How the entries are arranged in SVG and PNG output
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    /// Indented rows with hierarchy lines
+    List,
+    /// Nested rectangles with areas proportional to `--area-by`
+    Treemap,
+    /// Rings around the root with angles proportional to `--area-by`
+    Sunburst,
+    /// Boxes with the root at the top and children fanning out below
+    OrgChart,
+    /// Boxes with the root at the left and children fanning out to the right
+    OrgChartHorizontal,
+    /// Boxes with the root in the middle and branches to its left and right
+    MindMap,
+    /// Folders as containers with their children flowing inside them
+    NestedBoxes,
+    /// A column per level along the `--focus` paths, like a file browser
+    MillerColumns,
+}
+
+impl FromStr for Layout {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "list" => Ok(Layout::List),
+            "treemap" => Ok(Layout::Treemap),
+            "sunburst" => Ok(Layout::Sunburst),
+            "org-chart" => Ok(Layout::OrgChart),
+            "org-chart-horizontal" => Ok(Layout::OrgChartHorizontal),
+            "mind-map" => Ok(Layout::MindMap),
+            "nested-boxes" => Ok(Layout::NestedBoxes),
+            "miller-columns" => Ok(Layout::MillerColumns),
+            _ => Err(
+                "Invalid layout. Choose from 'list', 'treemap', 'sunburst', 'org-chart', 'org-chart-horizontal', 'mind-map', 'nested-boxes', 'miller-columns'.",
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Layout::List => "list",
+                Layout::Treemap => "treemap",
+                Layout::Sunburst => "sunburst",
+                Layout::OrgChart => "org-chart",
+                Layout::OrgChartHorizontal => "org-chart-horizontal",
+                Layout::MindMap => "mind-map",
+                Layout::NestedBoxes => "nested-boxes",
+                Layout::MillerColumns => "miller-columns",
+            }
+        )
+    }
+}
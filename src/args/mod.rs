@@ -1,61 +1,189 @@
 pub mod themes;
 use themes::Themes;
+pub mod area_by;
+pub mod color_by;
 pub mod filetypes;
-use clap::Parser;
+pub mod layout;
+pub mod size_mode;
+use crate::annotations::parse_inline_annotation;
+use crate::fs_parser::git_history::parse_since;
+use area_by::AreaBy;
+use clap::{Parser, Subcommand};
+use color_by::ColorBy;
 use filetypes::Filetype;
+use layout::Layout;
+use size_mode::SizeMode;
 use std::path::PathBuf;
 
 /// Glimmr
 /// -
 /// Create beautiful visualisations of filestructures, fast and easy.
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, subcommand_negates_reqs = true)]
 pub struct Args {
-    /// Path to the directory to visualize
-    #[arg()]
-    pub input_path: PathBuf,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Path to the directory or archive (tar, tar.gz/xz/zst, zip) to visualize. Use `-` to read a path list from stdin
+    #[arg(required_unless_present_any = ["from_paths", "from_tree", "git_rev"])]
+    pub input_path: Option<PathBuf>,
+
+    /// Visualize a newline- or NUL-separated list of relative paths read from a file (`-` for stdin)
+    #[arg(long)]
+    pub from_paths: Option<PathBuf>,
+
+    /// Visualize a hand-authored tree, or `tree`, `tree -J` or `tree -X` output read from a file (`-` for stdin)
+    #[arg(long, conflicts_with = "from_paths")]
+    pub from_tree: Option<PathBuf>,
+
+    /// Visualize the tree of a git revision (e.g. `v1.0`) of the repository containing input_path
+    #[arg(long, conflicts_with_all = ["from_paths", "from_tree"])]
+    pub git_rev: Option<String>,
 
     /// Theme to use
-    #[arg(short = 't', long, default_value = "default")]
+    #[arg(short = 't', long, default_value = "default", global = true)]
     pub theme: Themes,
 
     /// Output filetype
-    #[arg(short = 'f', long, default_value_t = Filetype::SVG)]
+    #[arg(short = 'f', long, default_value_t = Filetype::SVG, global = true)]
     pub filetype: Filetype,
 
     /// Output filename or filepath
-    #[arg(short = 'o', long, default_value = "glimmr_out")]
+    #[arg(short = 'o', long, default_value = "glimmr_out", global = true)]
     pub output_filepath: String,
 
     /// Max recursive depth
-    #[arg(short = 'd', long, default_value_t = 3)]
+    #[arg(short = 'd', long, default_value_t = 3, global = true)]
     pub depth: u32,
 
     /// Include root folder
-    #[arg(short = 'r', long)]
+    #[arg(short = 'r', long, global = true)]
     pub include_root: bool,
 
+    /// Arrangement of the entries in svg and png output: `list`, `treemap`, `sunburst`,
+    /// `org-chart`, `org-chart-horizontal`, `mind-map`, `nested-boxes` or `miller-columns`
+    #[arg(long, default_value_t = Layout::List, global = true)]
+    pub layout: Layout,
+
+    /// What the area of entries is proportional to in the treemap and sunburst: `files`, `size` or `loc`
+    #[arg(long, default_value_t = AreaBy::Size, global = true)]
+    pub area_by: AreaBy,
+
+    /// Wrap the rows of the list layout into this many side by side columns
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with = "max_rows",
+        global = true
+    )]
+    pub columns: Option<u32>,
+
+    /// Wrap the rows of the list layout into columns of at most this many rows
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..), global = true)]
+    pub max_rows: Option<u32>,
+
+    /// Split the output into pages of at most this many rows, written as numbered files
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u32).range(1..),
+        conflicts_with_all = ["columns", "max_rows"],
+        global = true
+    )]
+    pub page_rows: Option<u32>,
+
     /// Dont render background color
-    #[arg(short = 'b', long)]
+    #[arg(short = 'b', long, global = true)]
     pub bg_transparent: bool,
 
     /// Bake font into svg
-    #[arg(short = 'F', long, default_value_t = false)]
+    #[arg(short = 'F', long, default_value_t = false, global = true)]
     pub bake_font: bool,
 
     /// Files/Folders to exclude
-    #[arg(short = 'e', long, num_args = 0..)]
+    #[arg(short = 'e', long, num_args = 0.., global = true)]
     pub excludes: Vec<PathBuf>,
 
     /// Use .gitignore files for exclusion
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub use_gitignore: bool,
 
     /// Ignore hidden files and directories
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub ignore_hidden: bool,
 
     /// Collapse folder paths that only contain a single folder
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub collapse_folders: bool,
+
+    /// Only expand these paths (relative to input_path). Siblings along the way are shown collapsed
+    #[arg(long, global = true)]
+    pub focus: Vec<PathBuf>,
+
+    /// Highlight entries matching these glob patterns, along with the lines leading to them
+    #[arg(long, global = true)]
+    pub highlight: Vec<String>,
+
+    /// Render entries that are not highlighted at reduced opacity
+    #[arg(long, requires = "highlight", global = true)]
+    pub dim_unhighlighted: bool,
+
+    /// YAML, JSON or TOML file mapping paths to annotations
+    #[arg(long, global = true)]
+    pub annotations: Option<PathBuf>,
+
+    /// Annotate a path, e.g. `--annotate "src/fs_parser=walks the filesystem"`
+    #[arg(long, value_parser = parse_inline_annotation, global = true)]
+    pub annotate: Vec<(PathBuf, String)>,
+
+    /// Mark modified, staged, untracked and conflicted entries of the repo containing the input
+    #[arg(long, conflicts_with_all = ["git_rev", "from_paths", "from_tree"])]
+    pub git_status: bool,
+
+    /// Show the owners from `.github/CODEOWNERS`, `CODEOWNERS` or `docs/CODEOWNERS` of the repo
+    #[arg(long)]
+    pub owners: bool,
+
+    /// Show line coverage from an lcov or Cobertura XML report
+    #[arg(long)]
+    pub coverage: Option<PathBuf>,
+
+    /// Count diagnostics from a SARIF file or `cargo --message-format=json` output (`-` for stdin)
+    #[arg(long)]
+    pub diagnostics: Option<PathBuf>,
+
+    /// Count code, comment and blank lines per file and folder
+    #[arg(long)]
+    pub loc: bool,
+
+    /// Show file sizes and folder totals, measured as `apparent` size (default) or on `disk`
+    #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "apparent")]
+    pub sizes: Option<SizeMode>,
+
+    /// Order siblings by size, largest first
+    #[arg(long, requires = "sizes")]
+    pub sort_by_size: bool,
+
+    /// Color entry backgrounds by git history: `commits` or changed `lines` per path, or `age`
+    #[arg(long)]
+    pub color_by: Option<ColorBy>,
+
+    /// With `commits` or `lines`, only count history since a date (`2024-01-31`) or duration (`90d`, `12w`, `6m`, `1y`)
+    #[arg(long, value_parser = parse_since, requires = "color_by")]
+    pub since: Option<i64>,
+
+    /// With `commits` or `lines`, only count history of a revision or range, e.g. `v1.0..HEAD`
+    #[arg(long, requires = "color_by")]
+    pub rev_range: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Visualize what was added, removed, renamed or modified between two trees
+    Diff {
+        /// Old side: a directory, archive, snapshot JSON (`--filetype json`) or git revision
+        old: String,
+
+        /// New side: a directory, archive, snapshot JSON (`--filetype json`) or git revision
+        new: String,
+    },
 }
@@ -0,0 +1,35 @@
+use std::str::FromStr;
+
+/// How the size of a file is measured with `--sizes`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SizeMode {
+    /// Length of the content in bytes
+    Apparent,
+    /// Space the file takes up on disk. Only known for directories.
+    Disk,
+}
+
+impl FromStr for SizeMode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "apparent" => Ok(SizeMode::Apparent),
+            "disk" => Ok(SizeMode::Disk),
+            _ => Err("Invalid size mode. Choose from 'apparent', 'disk'."),
+        }
+    }
+}
+
+impl std::fmt::Display for SizeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SizeMode::Apparent => "apparent",
+                SizeMode::Disk => "disk",
+            }
+        )
+    }
+}
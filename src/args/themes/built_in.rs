@@ -21,8 +21,60 @@ pub static THEMES: Lazy<HashMap<BuiltInThemes, Theme>> = Lazy::new(|| {
             bg_corner_rad: 0,
             folder_bg_corner_rad: 2,
             file_bg_corner_rad: 2,
+            container_padding: 8,
+            container_header_height: 24,
             hierarchy_line: HierarchyLineStyles::Default,
             hierarchy_line_color: "#555555".into(),
+            highlight_text_color: "#111111".into(),
+            highlight_bg_color: "#FFE08A".into(),
+            highlight_line_color: "#D98E04".into(),
+            dim_opacity: 0.35,
+            annotation_text_color: "#888888".into(),
+            annotation_font_size: 12,
+            diff_added_color: "#1A7F37".into(),
+            diff_removed_color: "#CF222E".into(),
+            diff_changed_color: "#9A6700".into(),
+            git_modified_color: "#9A6700".into(),
+            git_staged_color: "#1A7F37".into(),
+            git_untracked_color: "#0969DA".into(),
+            git_conflicted_color: "#CF222E".into(),
+            heatmap_colors: vec![
+                "#FFF5CC".into(),
+                "#FFE08A".into(),
+                "#FFC078".into(),
+                "#FF9E80".into(),
+                "#FF6B6B".into(),
+            ],
+            age_colors: vec![
+                "#B2F2BB".into(),
+                "#D8F5A2".into(),
+                "#FFF3BF".into(),
+                "#FFE8CC".into(),
+                "#E9ECEF".into(),
+            ],
+            age_thresholds: vec![7, 30, 180, 365],
+            age_dim_after: 730,
+            owner_colors: vec![
+                "#0969DA".into(),
+                "#8250DF".into(),
+                "#1A7F37".into(),
+                "#BF3989".into(),
+                "#9A6700".into(),
+                "#1B7C83".into(),
+                "#BC4C00".into(),
+                "#57606A".into(),
+            ],
+            unowned_color: "#CF222E".into(),
+            coverage_colors: vec![
+                "#CF222E".into(),
+                "#BC4C00".into(),
+                "#9A6700".into(),
+                "#1A7F37".into(),
+            ],
+            diagnostic_error_color: "#CF222E".into(),
+            diagnostic_warning_color: "#9A6700".into(),
+            diagnostic_note_color: "#0969DA".into(),
+            size_bar_color: "#DBEAFE".into(),
         },
     );
 
@@ -40,8 +92,60 @@ pub static THEMES: Lazy<HashMap<BuiltInThemes, Theme>> = Lazy::new(|| {
             bg_corner_rad: 0,
             folder_bg_corner_rad: 2,
             file_bg_corner_rad: 2,
+            container_padding: 8,
+            container_header_height: 24,
             hierarchy_line: HierarchyLineStyles::Default,
             hierarchy_line_color: "#DDDDDD".into(),
+            highlight_text_color: "#FFFFFF".into(),
+            highlight_bg_color: "#806000".into(),
+            highlight_line_color: "#FFC940".into(),
+            dim_opacity: 0.35,
+            annotation_text_color: "#999999".into(),
+            annotation_font_size: 12,
+            diff_added_color: "#3FB950".into(),
+            diff_removed_color: "#F85149".into(),
+            diff_changed_color: "#D29922".into(),
+            git_modified_color: "#D29922".into(),
+            git_staged_color: "#3FB950".into(),
+            git_untracked_color: "#58A6FF".into(),
+            git_conflicted_color: "#F85149".into(),
+            heatmap_colors: vec![
+                "hsl(50, 40%, 22%)".into(),
+                "hsl(35, 50%, 26%)".into(),
+                "hsl(20, 55%, 30%)".into(),
+                "hsl(5, 60%, 33%)".into(),
+                "hsl(350, 65%, 36%)".into(),
+            ],
+            age_colors: vec![
+                "hsl(140, 45%, 24%)".into(),
+                "hsl(100, 30%, 22%)".into(),
+                "hsl(55, 30%, 20%)".into(),
+                "hsl(30, 25%, 20%)".into(),
+                "hsl(0, 0%, 20%)".into(),
+            ],
+            age_thresholds: vec![7, 30, 180, 365],
+            age_dim_after: 730,
+            owner_colors: vec![
+                "#58A6FF".into(),
+                "#BC8CFF".into(),
+                "#3FB950".into(),
+                "#FF7B72".into(),
+                "#D29922".into(),
+                "#39C5CF".into(),
+                "#F0883E".into(),
+                "#8B949E".into(),
+            ],
+            unowned_color: "#F85149".into(),
+            coverage_colors: vec![
+                "#F85149".into(),
+                "#F0883E".into(),
+                "#D29922".into(),
+                "#3FB950".into(),
+            ],
+            diagnostic_error_color: "#F85149".into(),
+            diagnostic_warning_color: "#D29922".into(),
+            diagnostic_note_color: "#58A6FF".into(),
+            size_bar_color: "#1E3A5F".into(),
         },
     );
 
@@ -59,8 +163,60 @@ pub static THEMES: Lazy<HashMap<BuiltInThemes, Theme>> = Lazy::new(|| {
             bg_corner_rad: 0,
             folder_bg_corner_rad: 3,
             file_bg_corner_rad: 3,
+            container_padding: 8,
+            container_header_height: 24,
             hierarchy_line: HierarchyLineStyles::Default,
             hierarchy_line_color: "hsl(203, 50%, 45%)".into(),
+            highlight_text_color: "hsl(35, 90%, 85%)".into(),
+            highlight_bg_color: "hsl(35, 70%, 30%)".into(),
+            highlight_line_color: "hsl(35, 90%, 60%)".into(),
+            dim_opacity: 0.35,
+            annotation_text_color: "hsl(203, 30%, 55%)".into(),
+            annotation_font_size: 12,
+            diff_added_color: "hsl(95, 50%, 62%)".into(),
+            diff_removed_color: "hsl(350, 80%, 70%)".into(),
+            diff_changed_color: "hsl(40, 70%, 65%)".into(),
+            git_modified_color: "hsl(40, 70%, 65%)".into(),
+            git_staged_color: "hsl(95, 50%, 62%)".into(),
+            git_untracked_color: "hsl(217, 92%, 76%)".into(),
+            git_conflicted_color: "hsl(350, 80%, 70%)".into(),
+            heatmap_colors: vec![
+                "hsl(220, 35%, 24%)".into(),
+                "hsl(260, 35%, 28%)".into(),
+                "hsl(300, 35%, 30%)".into(),
+                "hsl(335, 45%, 33%)".into(),
+                "hsl(355, 60%, 36%)".into(),
+            ],
+            age_colors: vec![
+                "hsl(95, 40%, 24%)".into(),
+                "hsl(170, 35%, 22%)".into(),
+                "hsl(203, 35%, 20%)".into(),
+                "hsl(225, 25%, 18%)".into(),
+                "hsl(203, 15%, 16%)".into(),
+            ],
+            age_thresholds: vec![7, 30, 180, 365],
+            age_dim_after: 730,
+            owner_colors: vec![
+                "hsl(217, 92%, 76%)".into(),
+                "hsl(267, 84%, 81%)".into(),
+                "hsl(95, 50%, 62%)".into(),
+                "hsl(199, 91%, 64%)".into(),
+                "hsl(40, 70%, 65%)".into(),
+                "hsl(174, 60%, 60%)".into(),
+                "hsl(24, 98%, 70%)".into(),
+                "hsl(330, 70%, 75%)".into(),
+            ],
+            unowned_color: "hsl(350, 80%, 70%)".into(),
+            coverage_colors: vec![
+                "hsl(350, 80%, 70%)".into(),
+                "hsl(24, 98%, 70%)".into(),
+                "hsl(40, 70%, 65%)".into(),
+                "hsl(95, 50%, 62%)".into(),
+            ],
+            diagnostic_error_color: "hsl(350, 80%, 70%)".into(),
+            diagnostic_warning_color: "hsl(40, 70%, 65%)".into(),
+            diagnostic_note_color: "hsl(217, 92%, 76%)".into(),
+            size_bar_color: "hsl(220, 50%, 26%)".into(),
         },
     );
 
@@ -78,8 +234,60 @@ pub static THEMES: Lazy<HashMap<BuiltInThemes, Theme>> = Lazy::new(|| {
             bg_corner_rad: 0,
             folder_bg_corner_rad: 2,
             file_bg_corner_rad: 2,
+            container_padding: 8,
+            container_header_height: 24,
             hierarchy_line: HierarchyLineStyles::Default,
             hierarchy_line_color: "hsl(288, 50%, 60%)".into(),
+            highlight_text_color: "hsl(326, 100%, 88%)".into(),
+            highlight_bg_color: "hsl(326, 60%, 30%)".into(),
+            highlight_line_color: "hsl(326, 100%, 74%)".into(),
+            dim_opacity: 0.35,
+            annotation_text_color: "hsl(288, 20%, 55%)".into(),
+            annotation_font_size: 12,
+            diff_added_color: "hsl(135, 94%, 65%)".into(),
+            diff_removed_color: "hsl(0, 100%, 67%)".into(),
+            diff_changed_color: "hsl(65, 92%, 76%)".into(),
+            git_modified_color: "hsl(31, 100%, 71%)".into(),
+            git_staged_color: "hsl(135, 94%, 65%)".into(),
+            git_untracked_color: "hsl(191, 97%, 77%)".into(),
+            git_conflicted_color: "hsl(0, 100%, 67%)".into(),
+            heatmap_colors: vec![
+                "hsl(265, 30%, 22%)".into(),
+                "hsl(282, 40%, 26%)".into(),
+                "hsl(310, 50%, 30%)".into(),
+                "hsl(330, 60%, 34%)".into(),
+                "hsl(0, 70%, 38%)".into(),
+            ],
+            age_colors: vec![
+                "hsl(135, 45%, 24%)".into(),
+                "hsl(190, 40%, 24%)".into(),
+                "hsl(230, 30%, 22%)".into(),
+                "hsl(265, 25%, 18%)".into(),
+                "hsl(282, 10%, 16%)".into(),
+            ],
+            age_thresholds: vec![7, 30, 180, 365],
+            age_dim_after: 730,
+            owner_colors: vec![
+                "hsl(265, 89%, 78%)".into(),
+                "hsl(191, 97%, 77%)".into(),
+                "hsl(135, 94%, 65%)".into(),
+                "hsl(326, 100%, 74%)".into(),
+                "hsl(31, 100%, 71%)".into(),
+                "hsl(65, 92%, 76%)".into(),
+                "hsl(225, 27%, 70%)".into(),
+                "hsl(0, 100%, 67%)".into(),
+            ],
+            unowned_color: "hsl(0, 100%, 67%)".into(),
+            coverage_colors: vec![
+                "hsl(0, 100%, 67%)".into(),
+                "hsl(31, 100%, 71%)".into(),
+                "hsl(65, 92%, 76%)".into(),
+                "hsl(135, 94%, 65%)".into(),
+            ],
+            diagnostic_error_color: "hsl(0, 100%, 67%)".into(),
+            diagnostic_warning_color: "hsl(31, 100%, 71%)".into(),
+            diagnostic_note_color: "hsl(191, 97%, 77%)".into(),
+            size_bar_color: "hsl(265, 45%, 28%)".into(),
         },
     );
 
@@ -97,8 +305,60 @@ pub static THEMES: Lazy<HashMap<BuiltInThemes, Theme>> = Lazy::new(|| {
             bg_corner_rad: 0,
             folder_bg_corner_rad: 2,
             file_bg_corner_rad: 2,
+            container_padding: 8,
+            container_header_height: 24,
             hierarchy_line: HierarchyLineStyles::Default,
             hierarchy_line_color: "hsl(45, 60%, 55%)".into(),
+            highlight_text_color: "hsl(18, 80%, 85%)".into(),
+            highlight_bg_color: "hsl(18, 60%, 25%)".into(),
+            highlight_line_color: "hsl(18, 80%, 55%)".into(),
+            dim_opacity: 0.35,
+            annotation_text_color: "hsl(45, 20%, 50%)".into(),
+            annotation_font_size: 12,
+            diff_added_color: "hsl(68, 100%, 30%)".into(),
+            diff_removed_color: "hsl(1, 71%, 52%)".into(),
+            diff_changed_color: "hsl(45, 100%, 35%)".into(),
+            git_modified_color: "hsl(45, 100%, 35%)".into(),
+            git_staged_color: "hsl(68, 100%, 30%)".into(),
+            git_untracked_color: "hsl(205, 69%, 49%)".into(),
+            git_conflicted_color: "hsl(1, 71%, 52%)".into(),
+            heatmap_colors: vec![
+                "hsl(45, 60%, 20%)".into(),
+                "hsl(30, 65%, 24%)".into(),
+                "hsl(18, 70%, 28%)".into(),
+                "hsl(1, 65%, 32%)".into(),
+                "hsl(331, 60%, 34%)".into(),
+            ],
+            age_colors: vec![
+                "hsl(68, 60%, 22%)".into(),
+                "hsl(175, 45%, 22%)".into(),
+                "hsl(205, 45%, 22%)".into(),
+                "hsl(200, 30%, 16%)".into(),
+                "hsl(200, 15%, 14%)".into(),
+            ],
+            age_thresholds: vec![7, 30, 180, 365],
+            age_dim_after: 730,
+            owner_colors: vec![
+                "hsl(205, 69%, 49%)".into(),
+                "hsl(237, 43%, 60%)".into(),
+                "hsl(68, 100%, 30%)".into(),
+                "hsl(331, 64%, 52%)".into(),
+                "hsl(45, 100%, 35%)".into(),
+                "hsl(175, 59%, 40%)".into(),
+                "hsl(18, 80%, 44%)".into(),
+                "hsl(186, 8%, 55%)".into(),
+            ],
+            unowned_color: "hsl(1, 71%, 52%)".into(),
+            coverage_colors: vec![
+                "hsl(1, 71%, 52%)".into(),
+                "hsl(18, 80%, 44%)".into(),
+                "hsl(45, 100%, 35%)".into(),
+                "hsl(68, 100%, 30%)".into(),
+            ],
+            diagnostic_error_color: "hsl(1, 71%, 52%)".into(),
+            diagnostic_warning_color: "hsl(45, 100%, 35%)".into(),
+            diagnostic_note_color: "hsl(205, 69%, 49%)".into(),
+            size_bar_color: "hsl(190, 60%, 22%)".into(),
         },
     );
 
@@ -116,8 +376,60 @@ pub static THEMES: Lazy<HashMap<BuiltInThemes, Theme>> = Lazy::new(|| {
             bg_corner_rad: 0,
             folder_bg_corner_rad: 2,
             file_bg_corner_rad: 2,
+            container_padding: 8,
+            container_header_height: 24,
             hierarchy_line: HierarchyLineStyles::Default,
             hierarchy_line_color: "hsl(48, 100%, 60%)".into(),
+            highlight_text_color: "hsl(80, 76%, 80%)".into(),
+            highlight_bg_color: "hsl(80, 60%, 22%)".into(),
+            highlight_line_color: "hsl(80, 76%, 53%)".into(),
+            dim_opacity: 0.35,
+            annotation_text_color: "hsl(48, 30%, 50%)".into(),
+            annotation_font_size: 12,
+            diff_added_color: "hsl(80, 76%, 53%)".into(),
+            diff_removed_color: "hsl(338, 95%, 56%)".into(),
+            diff_changed_color: "hsl(54, 70%, 68%)".into(),
+            git_modified_color: "hsl(32, 98%, 56%)".into(),
+            git_staged_color: "hsl(80, 76%, 53%)".into(),
+            git_untracked_color: "hsl(190, 81%, 67%)".into(),
+            git_conflicted_color: "hsl(338, 95%, 56%)".into(),
+            heatmap_colors: vec![
+                "hsl(54, 45%, 22%)".into(),
+                "hsl(40, 55%, 26%)".into(),
+                "hsl(25, 65%, 30%)".into(),
+                "hsl(338, 60%, 32%)".into(),
+                "hsl(325, 70%, 36%)".into(),
+            ],
+            age_colors: vec![
+                "hsl(80, 60%, 24%)".into(),
+                "hsl(60, 40%, 22%)".into(),
+                "hsl(190, 40%, 22%)".into(),
+                "hsl(261, 30%, 22%)".into(),
+                "hsl(290, 15%, 16%)".into(),
+            ],
+            age_thresholds: vec![7, 30, 180, 365],
+            age_dim_after: 730,
+            owner_colors: vec![
+                "hsl(190, 81%, 67%)".into(),
+                "hsl(261, 100%, 75%)".into(),
+                "hsl(80, 76%, 53%)".into(),
+                "hsl(338, 95%, 56%)".into(),
+                "hsl(54, 70%, 68%)".into(),
+                "hsl(32, 98%, 56%)".into(),
+                "hsl(190, 50%, 50%)".into(),
+                "hsl(55, 8%, 60%)".into(),
+            ],
+            unowned_color: "hsl(338, 95%, 56%)".into(),
+            coverage_colors: vec![
+                "hsl(338, 95%, 56%)".into(),
+                "hsl(32, 98%, 56%)".into(),
+                "hsl(54, 70%, 68%)".into(),
+                "hsl(80, 76%, 53%)".into(),
+            ],
+            diagnostic_error_color: "hsl(338, 95%, 56%)".into(),
+            diagnostic_warning_color: "hsl(32, 98%, 56%)".into(),
+            diagnostic_note_color: "hsl(190, 81%, 67%)".into(),
+            size_bar_color: "hsl(80, 35%, 24%)".into(),
         },
     );
 
@@ -135,8 +447,60 @@ pub static THEMES: Lazy<HashMap<BuiltInThemes, Theme>> = Lazy::new(|| {
             bg_corner_rad: 0,
             folder_bg_corner_rad: 2,
             file_bg_corner_rad: 2,
+            container_padding: 8,
+            container_header_height: 24,
             hierarchy_line: HierarchyLineStyles::Default,
             hierarchy_line_color: "hsl(210, 30%, 55%)".into(),
+            highlight_text_color: "hsl(40, 70%, 85%)".into(),
+            highlight_bg_color: "hsl(40, 40%, 30%)".into(),
+            highlight_line_color: "hsl(40, 70%, 73%)".into(),
+            dim_opacity: 0.35,
+            annotation_text_color: "hsl(210, 15%, 55%)".into(),
+            annotation_font_size: 12,
+            diff_added_color: "hsl(92, 28%, 65%)".into(),
+            diff_removed_color: "hsl(354, 42%, 56%)".into(),
+            diff_changed_color: "hsl(40, 71%, 73%)".into(),
+            git_modified_color: "hsl(40, 71%, 73%)".into(),
+            git_staged_color: "hsl(92, 28%, 65%)".into(),
+            git_untracked_color: "hsl(193, 43%, 67%)".into(),
+            git_conflicted_color: "hsl(354, 42%, 56%)".into(),
+            heatmap_colors: vec![
+                "hsl(40, 30%, 28%)".into(),
+                "hsl(28, 35%, 30%)".into(),
+                "hsl(14, 40%, 32%)".into(),
+                "hsl(354, 40%, 34%)".into(),
+                "hsl(311, 25%, 36%)".into(),
+            ],
+            age_colors: vec![
+                "hsl(92, 28%, 30%)".into(),
+                "hsl(179, 25%, 30%)".into(),
+                "hsl(193, 30%, 30%)".into(),
+                "hsl(213, 25%, 26%)".into(),
+                "hsl(220, 15%, 22%)".into(),
+            ],
+            age_thresholds: vec![7, 30, 180, 365],
+            age_dim_after: 730,
+            owner_colors: vec![
+                "hsl(193, 43%, 67%)".into(),
+                "hsl(311, 20%, 63%)".into(),
+                "hsl(92, 28%, 65%)".into(),
+                "hsl(210, 34%, 63%)".into(),
+                "hsl(40, 71%, 73%)".into(),
+                "hsl(179, 25%, 65%)".into(),
+                "hsl(14, 51%, 63%)".into(),
+                "hsl(213, 32%, 52%)".into(),
+            ],
+            unowned_color: "hsl(354, 42%, 56%)".into(),
+            coverage_colors: vec![
+                "hsl(354, 42%, 56%)".into(),
+                "hsl(14, 51%, 63%)".into(),
+                "hsl(40, 71%, 73%)".into(),
+                "hsl(92, 28%, 65%)".into(),
+            ],
+            diagnostic_error_color: "hsl(354, 42%, 56%)".into(),
+            diagnostic_warning_color: "hsl(40, 71%, 73%)".into(),
+            diagnostic_note_color: "hsl(193, 43%, 67%)".into(),
+            size_bar_color: "hsl(210, 35%, 32%)".into(),
         },
     );
 
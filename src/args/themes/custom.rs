@@ -17,7 +17,33 @@ pub struct PartialTheme {
     pub bg_corner_rad: Option<u32>,
     pub folder_bg_corner_rad: Option<u32>,
     pub file_bg_corner_rad: Option<u32>,
+    pub container_padding: Option<u32>,
+    pub container_header_height: Option<u32>,
     pub hierarchy_line_color: Option<String>,
+    pub highlight_text_color: Option<String>,
+    pub highlight_bg_color: Option<String>,
+    pub highlight_line_color: Option<String>,
+    pub dim_opacity: Option<f32>,
+    pub annotation_text_color: Option<String>,
+    pub annotation_font_size: Option<u32>,
+    pub diff_added_color: Option<String>,
+    pub diff_removed_color: Option<String>,
+    pub diff_changed_color: Option<String>,
+    pub git_modified_color: Option<String>,
+    pub git_staged_color: Option<String>,
+    pub git_untracked_color: Option<String>,
+    pub git_conflicted_color: Option<String>,
+    pub heatmap_colors: Option<Vec<String>>,
+    pub age_colors: Option<Vec<String>>,
+    pub age_thresholds: Option<Vec<u32>>,
+    pub age_dim_after: Option<u32>,
+    pub owner_colors: Option<Vec<String>>,
+    pub unowned_color: Option<String>,
+    pub coverage_colors: Option<Vec<String>>,
+    pub diagnostic_error_color: Option<String>,
+    pub diagnostic_warning_color: Option<String>,
+    pub diagnostic_note_color: Option<String>,
+    pub size_bar_color: Option<String>,
 }
 
 impl Theme {
@@ -50,11 +76,104 @@ impl Theme {
             file_bg_corner_rad: overlay
                 .file_bg_corner_rad
                 .unwrap_or(self.file_bg_corner_rad),
+            container_padding: overlay.container_padding.unwrap_or(self.container_padding),
+            container_header_height: overlay
+                .container_header_height
+                .unwrap_or(self.container_header_height),
             hierarchy_line: self.hierarchy_line.clone(),
             hierarchy_line_color: overlay
                 .hierarchy_line_color
                 .clone()
                 .unwrap_or_else(|| self.hierarchy_line_color.clone()),
+            highlight_text_color: overlay
+                .highlight_text_color
+                .clone()
+                .unwrap_or_else(|| self.highlight_text_color.clone()),
+            highlight_bg_color: overlay
+                .highlight_bg_color
+                .clone()
+                .unwrap_or_else(|| self.highlight_bg_color.clone()),
+            highlight_line_color: overlay
+                .highlight_line_color
+                .clone()
+                .unwrap_or_else(|| self.highlight_line_color.clone()),
+            dim_opacity: overlay.dim_opacity.unwrap_or(self.dim_opacity),
+            annotation_text_color: overlay
+                .annotation_text_color
+                .clone()
+                .unwrap_or_else(|| self.annotation_text_color.clone()),
+            annotation_font_size: overlay
+                .annotation_font_size
+                .unwrap_or(self.annotation_font_size),
+            diff_added_color: overlay
+                .diff_added_color
+                .clone()
+                .unwrap_or_else(|| self.diff_added_color.clone()),
+            diff_removed_color: overlay
+                .diff_removed_color
+                .clone()
+                .unwrap_or_else(|| self.diff_removed_color.clone()),
+            diff_changed_color: overlay
+                .diff_changed_color
+                .clone()
+                .unwrap_or_else(|| self.diff_changed_color.clone()),
+            git_modified_color: overlay
+                .git_modified_color
+                .clone()
+                .unwrap_or_else(|| self.git_modified_color.clone()),
+            git_staged_color: overlay
+                .git_staged_color
+                .clone()
+                .unwrap_or_else(|| self.git_staged_color.clone()),
+            git_untracked_color: overlay
+                .git_untracked_color
+                .clone()
+                .unwrap_or_else(|| self.git_untracked_color.clone()),
+            git_conflicted_color: overlay
+                .git_conflicted_color
+                .clone()
+                .unwrap_or_else(|| self.git_conflicted_color.clone()),
+            heatmap_colors: overlay
+                .heatmap_colors
+                .clone()
+                .unwrap_or_else(|| self.heatmap_colors.clone()),
+            age_colors: overlay
+                .age_colors
+                .clone()
+                .unwrap_or_else(|| self.age_colors.clone()),
+            age_thresholds: overlay
+                .age_thresholds
+                .clone()
+                .unwrap_or_else(|| self.age_thresholds.clone()),
+            age_dim_after: overlay.age_dim_after.unwrap_or(self.age_dim_after),
+            owner_colors: overlay
+                .owner_colors
+                .clone()
+                .unwrap_or_else(|| self.owner_colors.clone()),
+            unowned_color: overlay
+                .unowned_color
+                .clone()
+                .unwrap_or_else(|| self.unowned_color.clone()),
+            coverage_colors: overlay
+                .coverage_colors
+                .clone()
+                .unwrap_or_else(|| self.coverage_colors.clone()),
+            diagnostic_error_color: overlay
+                .diagnostic_error_color
+                .clone()
+                .unwrap_or_else(|| self.diagnostic_error_color.clone()),
+            diagnostic_warning_color: overlay
+                .diagnostic_warning_color
+                .clone()
+                .unwrap_or_else(|| self.diagnostic_warning_color.clone()),
+            diagnostic_note_color: overlay
+                .diagnostic_note_color
+                .clone()
+                .unwrap_or_else(|| self.diagnostic_note_color.clone()),
+            size_bar_color: overlay
+                .size_bar_color
+                .clone()
+                .unwrap_or_else(|| self.size_bar_color.clone()),
         }
     }
 }
@@ -7,7 +7,7 @@ use built_in::THEMES;
 #[derive(Debug, Clone, PartialEq, Display)]
 pub enum Themes {
     BuiltIn(BuiltInThemes),
-    Custom(Theme),
+    Custom(Box<Theme>),
 }
 
 #[derive(Debug, Clone, Display, EnumIter, EnumString, PartialEq, Eq, Hash)]
@@ -36,8 +36,34 @@ pub struct Theme {
     pub bg_corner_rad: u32,
     pub folder_bg_corner_rad: u32,
     pub file_bg_corner_rad: u32,
+    pub container_padding: u32,
+    pub container_header_height: u32,
     pub hierarchy_line: HierarchyLineStyles,
     pub hierarchy_line_color: String,
+    pub highlight_text_color: String,
+    pub highlight_bg_color: String,
+    pub highlight_line_color: String,
+    pub dim_opacity: f32,
+    pub annotation_text_color: String,
+    pub annotation_font_size: u32,
+    pub diff_added_color: String,
+    pub diff_removed_color: String,
+    pub diff_changed_color: String,
+    pub git_modified_color: String,
+    pub git_staged_color: String,
+    pub git_untracked_color: String,
+    pub git_conflicted_color: String,
+    pub heatmap_colors: Vec<String>,
+    pub age_colors: Vec<String>,
+    pub age_thresholds: Vec<u32>,
+    pub age_dim_after: u32,
+    pub owner_colors: Vec<String>,
+    pub unowned_color: String,
+    pub coverage_colors: Vec<String>,
+    pub diagnostic_error_color: String,
+    pub diagnostic_warning_color: String,
+    pub diagnostic_note_color: String,
+    pub size_bar_color: String,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -49,7 +75,7 @@ impl Themes {
     pub fn get_theme(&self) -> Theme {
         match self {
             Themes::BuiltIn(t) => THEMES.get(t).unwrap().clone(),
-            Themes::Custom(custom) => custom.clone(),
+            Themes::Custom(custom) => custom.as_ref().clone(),
         }
     }
 }
@@ -73,7 +99,7 @@ impl std::str::FromStr for Themes {
 
         // If not built-in, try custom theme
         match custom::load_custom_theme(s) {
-            Ok(theme) => Ok(Themes::Custom(theme)),
+            Ok(theme) => Ok(Themes::Custom(Box::new(theme))),
             Err(e) => Err(format!("Invalid theme input '{}'. Error: {}", s, e)),
         }
     }
@@ -0,0 +1,195 @@
+use crate::fs_parser::fs_structs::Coverage;
+use crate::fs_parser::resolve_report_path;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Loads line coverage per file from an lcov or Cobertura XML report. The format is detected
+/// from the content.
+///
+/// Report paths are matched to files below `root`, the visualized root, and returned relative
+/// to it.
+pub fn load_coverage(file: &Path, root: &Path) -> Result<HashMap<PathBuf, Coverage>, String> {
+    let content = fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read '{}': {}", file.display(), e))?;
+    let root = root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{}': {}", root.display(), e))?;
+
+    let report = if content.trim_start().starts_with('<') {
+        parse_cobertura(&content)?
+    } else {
+        parse_lcov(&content)
+    };
+
+    let mut coverage: HashMap<PathBuf, Coverage> = HashMap::new();
+    for (candidates, file_coverage) in report {
+        let Some(path) = candidates
+            .iter()
+            .find_map(|c| resolve_report_path(c, &root))
+        else {
+            continue;
+        };
+        let entry = coverage.entry(path).or_default();
+        entry.covered += file_coverage.covered;
+        entry.total += file_coverage.total;
+    }
+    Ok(coverage)
+}
+
+/// Parses an lcov tracefile. Uses the `LF`/`LH` summaries of a record if present, and counts
+/// its `DA` lines otherwise.
+fn parse_lcov(content: &str) -> Vec<(Vec<PathBuf>, Coverage)> {
+    let mut report = Vec::new();
+    let mut source: Option<PathBuf> = None;
+    let mut summary = (None, None);
+    let mut counted = Coverage::default();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(path) = line.strip_prefix("SF:") {
+            source = Some(PathBuf::from(path));
+            summary = (None, None);
+            counted = Coverage::default();
+        } else if let Some(found) = line.strip_prefix("LF:") {
+            summary.0 = found.parse().ok();
+        } else if let Some(hit) = line.strip_prefix("LH:") {
+            summary.1 = hit.parse().ok();
+        } else if let Some(data) = line.strip_prefix("DA:") {
+            counted.total += 1;
+            let hits = data.split(',').nth(1).and_then(|h| h.parse::<u64>().ok());
+            if hits.is_some_and(|hits| hits > 0) {
+                counted.covered += 1;
+            }
+        } else if line == "end_of_record"
+            && let Some(path) = source.take()
+        {
+            let file_coverage = match summary {
+                (Some(total), Some(covered)) => Coverage { covered, total },
+                _ => counted,
+            };
+            report.push((vec![path], file_coverage));
+        }
+    }
+    report
+}
+
+/// Parses a Cobertura XML report. File names of classes are relative to one of the report's
+/// sources, so every source is a candidate.
+fn parse_cobertura(content: &str) -> Result<Vec<(Vec<PathBuf>, Coverage)>, String> {
+    let document =
+        roxmltree::Document::parse(content).map_err(|e| format!("Failed to parse XML: {}", e))?;
+    let sources: Vec<PathBuf> = document
+        .descendants()
+        .filter(|n| n.has_tag_name("source"))
+        .filter_map(|n| n.text())
+        .map(|text| PathBuf::from(text.trim()))
+        .collect();
+
+    let mut report = Vec::new();
+    for class in document.descendants().filter(|n| n.has_tag_name("class")) {
+        let Some(filename) = class.attribute("filename") else {
+            continue;
+        };
+        let mut file_coverage = Coverage::default();
+        // Lines of methods are repeated in the class, so only direct lines are counted
+        for line in class
+            .children()
+            .filter(|n| n.has_tag_name("lines"))
+            .flat_map(|lines| lines.children().filter(|n| n.has_tag_name("line")))
+        {
+            file_coverage.total += 1;
+            let hits = line.attribute("hits").and_then(|h| h.parse::<u64>().ok());
+            if hits.is_some_and(|hits| hits > 0) {
+                file_coverage.covered += 1;
+            }
+        }
+
+        let mut candidates: Vec<PathBuf> = sources.iter().map(|s| s.join(filename)).collect();
+        candidates.push(PathBuf::from(filename));
+        report.push((candidates, file_coverage));
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lcov_prefers_summaries_over_counted_lines() {
+        let content = "\
+TN:
+SF:src/a.rs
+DA:1,3
+DA:2,0
+LF:10
+LH:7
+end_of_record
+SF:src/b.rs
+DA:1,1
+DA:2,0
+DA:3,2
+end_of_record
+";
+        let report = parse_lcov(content);
+        assert_eq!(
+            report,
+            [
+                (
+                    vec![PathBuf::from("src/a.rs")],
+                    Coverage {
+                        covered: 7,
+                        total: 10
+                    }
+                ),
+                (
+                    vec![PathBuf::from("src/b.rs")],
+                    Coverage {
+                        covered: 2,
+                        total: 3
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn lcov_skips_unterminated_records() {
+        assert!(parse_lcov("SF:src/a.rs\nDA:1,1\n").is_empty());
+    }
+
+    #[test]
+    fn cobertura_counts_class_lines_once() {
+        let content = r#"<?xml version="1.0"?>
+<coverage>
+  <sources><source>/repo/src</source></sources>
+  <packages><package><classes>
+    <class filename="a.py">
+      <methods><method><lines><line number="1" hits="1"/></lines></method></methods>
+      <lines>
+        <line number="1" hits="1"/>
+        <line number="2" hits="0"/>
+        <line number="3" hits="4"/>
+      </lines>
+    </class>
+  </classes></package></packages>
+</coverage>"#;
+        let report = parse_cobertura(content).unwrap();
+        assert_eq!(
+            report,
+            [(
+                vec![PathBuf::from("/repo/src/a.py"), PathBuf::from("a.py")],
+                Coverage {
+                    covered: 2,
+                    total: 3
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn cobertura_rejects_invalid_xml() {
+        assert!(parse_cobertura("<coverage>").is_err());
+    }
+}
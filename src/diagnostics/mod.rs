@@ -0,0 +1,131 @@
+use crate::fs_parser::fs_structs::Diagnostics;
+use crate::fs_parser::{read_source, resolve_report_path};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Counts diagnostics per file from a SARIF log or a stream of cargo JSON messages
+/// (`--message-format=json`). The format is detected from the content, `-` reads from stdin.
+///
+/// Report paths are matched to files below `root`, the visualized root, and returned relative
+/// to it. Diagnostics without a location are skipped.
+pub fn load_diagnostics(
+    source: &Path,
+    root: &Path,
+) -> Result<HashMap<PathBuf, Diagnostics>, String> {
+    let content = read_source(source)?;
+    let root = root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{}': {}", root.display(), e))?;
+
+    // A SARIF log is a single JSON object, cargo writes one object per line
+    let report = match serde_json::from_str::<Value>(&content) {
+        Ok(sarif) if sarif.get("runs").is_some() => parse_sarif(&sarif),
+        _ => parse_cargo_messages(&content),
+    };
+
+    let mut diagnostics: HashMap<PathBuf, Diagnostics> = HashMap::new();
+    for (path, level) in report {
+        let Some(path) = resolve_report_path(Path::new(&path), &root) else {
+            continue;
+        };
+        let counts = diagnostics.entry(path).or_default();
+        match level.as_str() {
+            "error" => counts.errors += 1,
+            "warning" => counts.warnings += 1,
+            _ => counts.notes += 1,
+        }
+    }
+    Ok(diagnostics)
+}
+
+/// Returns the file and level of every result in a SARIF log. Results without a level are
+/// warnings, as the SARIF spec defines.
+fn parse_sarif(sarif: &Value) -> Vec<(String, String)> {
+    let mut report = Vec::new();
+    for run in sarif["runs"].as_array().into_iter().flatten() {
+        for result in run["results"].as_array().into_iter().flatten() {
+            let level = result["level"].as_str().unwrap_or("warning");
+            if level == "none" {
+                continue;
+            }
+            let uri =
+                result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"].as_str();
+            if let Some(uri) = uri {
+                let path = uri.strip_prefix("file://").unwrap_or(uri);
+                report.push((path.to_string(), level.to_string()));
+            }
+        }
+    }
+    report
+}
+
+/// Returns the file of the primary span and the level of every compiler message in a cargo
+/// JSON stream. Other messages, like build script output, are skipped.
+fn parse_cargo_messages(content: &str) -> Vec<(String, String)> {
+    let mut report = Vec::new();
+    for line in content.lines() {
+        let Ok(message) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if message["reason"] != "compiler-message" {
+            continue;
+        }
+        let message = &message["message"];
+        let Some(level) = message["level"].as_str() else {
+            continue;
+        };
+        let primary_span = message["spans"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|span| span["is_primary"] == true);
+        if let Some(file) = primary_span.and_then(|span| span["file_name"].as_str()) {
+            report.push((file.to_string(), level.to_string()));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(report: &[(&str, &str)]) -> Vec<(String, String)> {
+        report
+            .iter()
+            .map(|(path, level)| (path.to_string(), level.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn sarif_defaults_to_warnings_and_skips_none() {
+        let sarif: Value = serde_json::from_str(
+            r#"{"runs": [{"results": [
+                {"level": "error", "locations": [{"physicalLocation": {"artifactLocation": {"uri": "file:///repo/a.rs"}}}]},
+                {"locations": [{"physicalLocation": {"artifactLocation": {"uri": "src/b.rs"}}}]},
+                {"level": "none", "locations": [{"physicalLocation": {"artifactLocation": {"uri": "src/c.rs"}}}]},
+                {"level": "note"}
+            ]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parse_sarif(&sarif),
+            pairs(&[("/repo/a.rs", "error"), ("src/b.rs", "warning")])
+        );
+    }
+
+    #[test]
+    fn cargo_messages_use_the_primary_span() {
+        let content = r#"{"reason": "compiler-artifact", "target": {}}
+{"reason": "compiler-message", "message": {"level": "warning", "spans": [{"file_name": "src/a.rs", "is_primary": false}, {"file_name": "src/b.rs", "is_primary": true}]}}
+{"reason": "compiler-message", "message": {"level": "error", "spans": []}}
+not json
+{"reason": "compiler-message", "message": {"level": "error", "spans": [{"file_name": "src/c.rs", "is_primary": true}]}}
+"#;
+        assert_eq!(
+            parse_cargo_messages(content),
+            pairs(&[("src/b.rs", "warning"), ("src/c.rs", "error")])
+        );
+    }
+}
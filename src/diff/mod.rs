@@ -0,0 +1,412 @@
+use crate::fs_parser::archive::{is_archive, read_archive};
+use crate::fs_parser::fs_structs::{DiffStatus, FlatFsEntry, FsEntryType};
+use crate::fs_parser::git_rev::read_git_tree;
+use crate::fs_parser::parse_fs_flat;
+use crate::fs_parser::snapshot::read_snapshot;
+use crate::fs_parser::synthetic::{SyntheticFilters, flatten_synthetic};
+use git2::{ObjectType, Oid};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Depth used to read both sides. The depth limit only applies to the union tree, so that
+/// changes deep down still mark their ancestors as modified.
+const FULL_DEPTH: u32 = u32::MAX - 1;
+
+/// Builds the union tree of two structures with every entry marked as added, removed, renamed,
+/// modified or unchanged. Unchanged folders are collapsed.
+///
+/// Each side can be a directory, an archive, a snapshot written with `--filetype json`, or a
+/// git revision. Revisions are read from the repository containing the other side if that is a
+/// directory, so only the same folder is compared, and from the current directory otherwise.
+/// Snapshots only go as deep as they were written, so neither side is compared below that.
+///
+/// # Arguments
+///
+/// * `old` - The side the changes are relative to.
+/// * `new` - The side with the changes.
+/// * `filters` - Which entries of the union tree are shown.
+/// * `use_gitignore` - Whether to respect `.gitignore` files for directory sides.
+pub fn diff_trees(
+    old: &str,
+    new: &str,
+    filters: &SyntheticFilters,
+    use_gitignore: bool,
+) -> Result<Vec<FlatFsEntry>, String> {
+    let repo_path = [new, old]
+        .into_iter()
+        .map(Path::new)
+        .find(|path| path.is_dir())
+        .unwrap_or(Path::new("."));
+    let read_side = |spec: &str| {
+        read_diff_side(spec, repo_path, filters, use_gitignore)
+            .map_err(|e| format!("Could not read '{}': {}", spec, e))
+    };
+    let mut old_entries = with_implied_folders(read_side(old)?);
+    let mut new_entries = with_implied_folders(read_side(new)?);
+    cut_at_depth_limits(&mut old_entries, &mut new_entries);
+
+    let mut union = union_with_status(old_entries, new_entries);
+    detect_renames(&mut union);
+    mark_modified_folders(&mut union);
+
+    let root_name = Path::new(new)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| new.to_string());
+    let flat_entries = flatten_synthetic(&root_name, union.into_values().collect(), filters, true);
+
+    Ok(collapse_unchanged(flat_entries))
+}
+
+/// Reads one side of the diff, at full depth and relative to its root. Revisions are read from
+/// the repository containing `repo_path`.
+fn read_diff_side(
+    spec: &str,
+    repo_path: &Path,
+    filters: &SyntheticFilters,
+    use_gitignore: bool,
+) -> Result<Vec<FlatFsEntry>, String> {
+    let path = Path::new(spec);
+
+    let entries = if path.is_dir() {
+        let mut entries = parse_fs_flat(
+            path.to_path_buf(),
+            FULL_DEPTH,
+            false,
+            filters.excludes.clone(),
+            use_gitignore,
+            filters.ignore_hidden,
+            Vec::new(),
+        );
+        hash_files(path, &mut entries);
+        return Ok(entries);
+    } else if is_archive(path) {
+        read_archive(path)?
+    } else if path.is_file() {
+        read_snapshot(path)?
+    } else {
+        read_git_tree(repo_path, spec, FULL_DEPTH)?
+    };
+
+    let filters = SyntheticFilters {
+        max_depth: FULL_DEPTH,
+        excludes: filters.excludes.clone(),
+        ignore_hidden: filters.ignore_hidden,
+        ..Default::default()
+    };
+    Ok(flatten_synthetic("", entries, &filters, true))
+}
+
+/// Drops everything below the folders either side didn't read because of its depth limit or
+/// focus paths, so a snapshot is only compared as deep as it goes
+fn cut_at_depth_limits(
+    old: &mut HashMap<PathBuf, FlatFsEntry>,
+    new: &mut HashMap<PathBuf, FlatFsEntry>,
+) {
+    let limits: HashSet<PathBuf> = old
+        .values()
+        .chain(new.values())
+        .filter(|entry| entry.depth_limited || entry.hides_children)
+        .map(|entry| entry.path.clone())
+        .collect();
+    if limits.is_empty() {
+        return;
+    }
+    let is_below_limit = |path: &Path| path.ancestors().skip(1).any(|a| limits.contains(a));
+    old.retain(|path, _| !is_below_limit(path));
+    new.retain(|path, _| !is_below_limit(path));
+}
+
+/// Hashes the files of a walked directory like git does, so directories can be compared with
+/// revisions and snapshots
+pub fn hash_files(root: &Path, entries: &mut [FlatFsEntry]) {
+    for entry in entries.iter_mut() {
+        if matches!(entry.entry_type, FsEntryType::File) {
+            entry.content_hash = Oid::hash_file(ObjectType::Blob, root.join(&entry.path))
+                .ok()
+                .map(|oid| oid.to_string());
+        }
+    }
+}
+
+/// Maps entries by path and adds the folders that are only implied by the paths of entries
+fn with_implied_folders(entries: Vec<FlatFsEntry>) -> HashMap<PathBuf, FlatFsEntry> {
+    let mut by_path = HashMap::new();
+    for entry in entries {
+        // The root entry is not compared
+        if entry.path.as_os_str().is_empty() {
+            continue;
+        }
+        for ancestor in entry.path.ancestors().skip(1) {
+            if ancestor.as_os_str().is_empty() {
+                break;
+            }
+            by_path
+                .entry(ancestor.to_path_buf())
+                .or_insert_with(|| FlatFsEntry {
+                    path: ancestor.to_path_buf(),
+                    entry_type: FsEntryType::Folder,
+                    ..Default::default()
+                });
+        }
+        by_path.insert(entry.path.clone(), entry);
+    }
+    by_path
+}
+
+/// Combines both sides, marking entries as added, removed, modified or unchanged
+fn union_with_status(
+    mut old: HashMap<PathBuf, FlatFsEntry>,
+    new: HashMap<PathBuf, FlatFsEntry>,
+) -> BTreeMap<PathBuf, FlatFsEntry> {
+    let mut union = BTreeMap::new();
+
+    for (path, new_entry) in new {
+        let status = match old.remove(&path) {
+            None => DiffStatus::Added,
+            Some(old_entry) if is_modified(&old_entry, &new_entry) => DiffStatus::Modified,
+            Some(_) => DiffStatus::Unchanged,
+        };
+        union.insert(
+            path,
+            FlatFsEntry {
+                diff: Some(status),
+                ..new_entry
+            },
+        );
+    }
+    for (path, old_entry) in old {
+        union.insert(
+            path,
+            FlatFsEntry {
+                diff: Some(DiffStatus::Removed),
+                ..old_entry
+            },
+        );
+    }
+
+    union
+}
+
+/// Compares two entries at the same path. Content hashes are used when both sides have them,
+/// sizes otherwise. Folders are compared by their contents later.
+fn is_modified(old: &FlatFsEntry, new: &FlatFsEntry) -> bool {
+    if old.entry_type != new.entry_type {
+        return true;
+    }
+    match (&old.content_hash, &new.content_hash) {
+        (Some(old_hash), Some(new_hash)) => old_hash != new_hash,
+        _ => old.size != new.size || old.link_target != new.link_target,
+    }
+}
+
+/// Pairs removed and added files with the same content. The added file is marked as renamed,
+/// the removed one is dropped.
+fn detect_renames(union: &mut BTreeMap<PathBuf, FlatFsEntry>) {
+    let mut removed_by_hash: HashMap<String, PathBuf> = HashMap::new();
+    for (path, entry) in union.iter() {
+        if entry.diff == Some(DiffStatus::Removed)
+            && matches!(entry.entry_type, FsEntryType::File)
+            && entry.size != Some(0)
+            && let Some(hash) = &entry.content_hash
+        {
+            removed_by_hash.insert(hash.clone(), path.clone());
+        }
+    }
+
+    let mut renamed_from = Vec::new();
+    for entry in union.values_mut() {
+        if entry.diff != Some(DiffStatus::Added) || !matches!(entry.entry_type, FsEntryType::File) {
+            continue;
+        }
+        if let Some(from) = entry
+            .content_hash
+            .as_ref()
+            .and_then(|hash| removed_by_hash.remove(hash))
+        {
+            entry.diff = Some(DiffStatus::Renamed(from.clone()));
+            renamed_from.push(from);
+        }
+    }
+
+    // The folder a file was moved out of has still changed
+    for from in renamed_from {
+        union.remove(&from);
+        if let Some(parent) = from.parent().and_then(|p| union.get_mut(p))
+            && parent.diff == Some(DiffStatus::Unchanged)
+        {
+            parent.diff = Some(DiffStatus::Modified);
+        }
+    }
+}
+
+/// Marks folders that exist on both sides as modified if anything inside them changed
+fn mark_modified_folders(union: &mut BTreeMap<PathBuf, FlatFsEntry>) {
+    let changed_ancestors: HashSet<PathBuf> = union
+        .iter()
+        .filter(|(_, entry)| entry.diff != Some(DiffStatus::Unchanged))
+        .flat_map(|(path, _)| path.ancestors().skip(1).map(Path::to_path_buf))
+        .collect();
+
+    for (path, entry) in union.iter_mut() {
+        if entry.diff == Some(DiffStatus::Unchanged) && changed_ancestors.contains(path) {
+            entry.diff = Some(DiffStatus::Modified);
+        }
+    }
+}
+
+/// Hides the contents of unchanged folders
+fn collapse_unchanged(filestructure: Vec<FlatFsEntry>) -> Vec<FlatFsEntry> {
+    let mut collapsed = Vec::new();
+    let mut skip_below: Option<u32> = None;
+
+    for (i, mut entry) in filestructure.iter().cloned().enumerate() {
+        if let Some(depth) = skip_below {
+            if entry.depth > depth {
+                continue;
+            }
+            skip_below = None;
+        }
+
+        let has_children = filestructure
+            .get(i + 1)
+            .is_some_and(|next| next.depth > entry.depth);
+        if matches!(entry.entry_type, FsEntryType::Folder)
+            && entry.diff == Some(DiffStatus::Unchanged)
+            && has_children
+        {
+            entry.hides_children = true;
+            skip_below = Some(entry.depth);
+        }
+        collapsed.push(entry);
+    }
+
+    collapsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, hash: &str) -> FlatFsEntry {
+        FlatFsEntry {
+            path: PathBuf::from(path),
+            entry_type: FsEntryType::File,
+            size: Some(hash.len() as u64),
+            content_hash: Some(hash.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Diffs two lists of entries the way `diff_trees` does, without reading any side
+    fn diff(old: Vec<FlatFsEntry>, new: Vec<FlatFsEntry>) -> BTreeMap<PathBuf, FlatFsEntry> {
+        let mut old = with_implied_folders(old);
+        let mut new = with_implied_folders(new);
+        cut_at_depth_limits(&mut old, &mut new);
+        let mut union = union_with_status(old, new);
+        detect_renames(&mut union);
+        mark_modified_folders(&mut union);
+        union
+    }
+
+    fn status(union: &BTreeMap<PathBuf, FlatFsEntry>, path: &str) -> Option<DiffStatus> {
+        union
+            .get(Path::new(path))
+            .and_then(|entry| entry.diff.clone())
+    }
+
+    #[test]
+    fn marks_added_removed_and_modified_files() {
+        let union = diff(
+            vec![file("a.rs", "1"), file("src/b.rs", "2"), file("c.rs", "3")],
+            vec![
+                file("a.rs", "1"),
+                file("src/b.rs", "changed"),
+                file("d.rs", "4"),
+            ],
+        );
+        assert_eq!(status(&union, "a.rs"), Some(DiffStatus::Unchanged));
+        assert_eq!(status(&union, "src/b.rs"), Some(DiffStatus::Modified));
+        assert_eq!(status(&union, "src"), Some(DiffStatus::Modified));
+        assert_eq!(status(&union, "c.rs"), Some(DiffStatus::Removed));
+        assert_eq!(status(&union, "d.rs"), Some(DiffStatus::Added));
+    }
+
+    #[test]
+    fn detects_renames_by_content() {
+        let union = diff(
+            vec![file("old/name.rs", "same"), file("old/keep.rs", "k")],
+            vec![file("new/name.rs", "same"), file("old/keep.rs", "k")],
+        );
+        assert_eq!(
+            status(&union, "new/name.rs"),
+            Some(DiffStatus::Renamed(PathBuf::from("old/name.rs")))
+        );
+        // The removed side of a rename is dropped, its folder has still changed
+        assert!(!union.contains_key(Path::new("old/name.rs")));
+        assert_eq!(status(&union, "old"), Some(DiffStatus::Modified));
+    }
+
+    #[test]
+    fn does_not_pair_empty_files_as_renames() {
+        let empty = |path| FlatFsEntry {
+            size: Some(0),
+            ..file(path, "e69de29")
+        };
+        let union = diff(vec![empty("a/__init__.py")], vec![empty("b/__init__.py")]);
+        assert_eq!(status(&union, "b/__init__.py"), Some(DiffStatus::Added));
+        assert_eq!(status(&union, "a/__init__.py"), Some(DiffStatus::Removed));
+    }
+
+    #[test]
+    fn compares_by_size_without_hashes() {
+        let sized = |path, size| FlatFsEntry {
+            path: PathBuf::from(path),
+            size: Some(size),
+            ..Default::default()
+        };
+        let union = diff(
+            vec![sized("a", 1), sized("b", 2)],
+            vec![sized("a", 1), sized("b", 3)],
+        );
+        assert_eq!(status(&union, "a"), Some(DiffStatus::Unchanged));
+        assert_eq!(status(&union, "b"), Some(DiffStatus::Modified));
+    }
+
+    #[test]
+    fn ignores_changes_below_the_depth_of_a_snapshot() {
+        let limited = FlatFsEntry {
+            path: PathBuf::from("x/y"),
+            entry_type: FsEntryType::Folder,
+            depth_limited: true,
+            ..Default::default()
+        };
+        let union = diff(
+            vec![limited],
+            vec![file("x/y/z/d.txt", "changed"), file("x/n.txt", "new")],
+        );
+        assert!(!union.contains_key(Path::new("x/y/z/d.txt")));
+        assert_eq!(status(&union, "x/y"), Some(DiffStatus::Unchanged));
+        assert_eq!(status(&union, "x/n.txt"), Some(DiffStatus::Added));
+    }
+
+    #[test]
+    fn collapses_unchanged_folders() {
+        let entry = |name: &str, depth, entry_type, diff| FlatFsEntry {
+            name: name.to_string(),
+            depth,
+            entry_type,
+            diff: Some(diff),
+            ..Default::default()
+        };
+        let collapsed = collapse_unchanged(vec![
+            entry("same", 0, FsEntryType::Folder, DiffStatus::Unchanged),
+            entry("a", 1, FsEntryType::File, DiffStatus::Unchanged),
+            entry("changed", 0, FsEntryType::Folder, DiffStatus::Modified),
+            entry("b", 1, FsEntryType::File, DiffStatus::Modified),
+        ]);
+        let names: Vec<&str> = collapsed.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["same", "changed", "b"]);
+        assert!(collapsed[0].hides_children);
+    }
+}
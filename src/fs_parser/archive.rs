@@ -0,0 +1,252 @@
+use super::fs_structs::{FlatFsEntry, FsEntryType};
+use chrono::NaiveDate;
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek};
+use std::path::{Path, PathBuf};
+
+enum ArchiveKind {
+    Tar,
+    TarGz,
+    TarXz,
+    TarZst,
+    Zip,
+}
+
+impl ArchiveKind {
+    /// Detects the archive kind from the file name
+    fn from_path(path: &Path) -> Option<ArchiveKind> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        let has_suffix = |suffixes: &[&str]| suffixes.iter().any(|s| name.ends_with(s));
+
+        if has_suffix(&[".tar"]) {
+            Some(ArchiveKind::Tar)
+        } else if has_suffix(&[".tar.gz", ".tgz", ".crate"]) {
+            Some(ArchiveKind::TarGz)
+        } else if has_suffix(&[".tar.xz", ".txz"]) {
+            Some(ArchiveKind::TarXz)
+        } else if has_suffix(&[".tar.zst", ".tzst"]) {
+            Some(ArchiveKind::TarZst)
+        } else if has_suffix(&[".zip", ".jar", ".war", ".ear", ".whl", ".apk", ".nupkg"]) {
+            Some(ArchiveKind::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns whether `path` is a file with the extension of a supported archive
+pub fn is_archive(path: &Path) -> bool {
+    path.is_file() && ArchiveKind::from_path(path).is_some()
+}
+
+/// Reads the entries of a tar (optionally gzip, xz or zstd compressed) or zip archive,
+/// without extracting it.
+///
+/// Entries carry their uncompressed size and modification time. Zip entries additionally carry
+/// their compressed size, which is not known per entry for compressed tar archives.
+pub fn read_archive(path: &Path) -> Result<Vec<FlatFsEntry>, String> {
+    let kind = ArchiveKind::from_path(path)
+        .ok_or_else(|| format!("Unsupported archive '{}'", path.display()))?;
+    let file = File::open(path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    read_entries(kind, BufReader::new(file))
+}
+
+fn read_entries<R: BufRead + Seek>(
+    kind: ArchiveKind,
+    reader: R,
+) -> Result<Vec<FlatFsEntry>, String> {
+    match kind {
+        ArchiveKind::Tar => read_tar(reader),
+        ArchiveKind::TarGz => read_tar(GzDecoder::new(reader)),
+        ArchiveKind::TarXz => read_tar(xz2::read::XzDecoder::new(reader)),
+        ArchiveKind::TarZst => read_tar(
+            zstd::stream::read::Decoder::with_buffer(reader)
+                .map_err(|e| format!("Failed to read zstd stream: {}", e))?,
+        ),
+        ArchiveKind::Zip => read_zip(reader),
+    }
+}
+
+fn read_tar<R: Read>(reader: R) -> Result<Vec<FlatFsEntry>, String> {
+    let mut archive = tar::Archive::new(reader);
+    let tar_entries = archive
+        .entries()
+        .map_err(|e| format!("Failed to read tar archive: {}", e))?;
+
+    let mut entries = Vec::new();
+    for tar_entry in tar_entries {
+        let tar_entry = tar_entry.map_err(|e| format!("Failed to read tar entry: {}", e))?;
+        let header = tar_entry.header();
+        let entry_type = header.entry_type();
+
+        // Skip pax headers and other metadata entries
+        if !(entry_type.is_file() || entry_type.is_dir() || entry_type.is_symlink()) {
+            continue;
+        }
+
+        let path: PathBuf = tar_entry
+            .path()
+            .map_err(|e| format!("Invalid path in tar archive: {}", e))?
+            .into_owned();
+        entries.push(FlatFsEntry {
+            path,
+            entry_type: if entry_type.is_dir() {
+                FsEntryType::Folder
+            } else {
+                FsEntryType::File
+            },
+            size: (!entry_type.is_dir()).then(|| header.size().ok()).flatten(),
+            modified: header.mtime().ok().map(|t| t as i64),
+            link_target: tar_entry
+                .link_name()
+                .ok()
+                .flatten()
+                .map(|target| target.to_string_lossy().into_owned()),
+            ..Default::default()
+        });
+    }
+
+    Ok(entries)
+}
+
+fn read_zip<R: Read + Seek>(reader: R) -> Result<Vec<FlatFsEntry>, String> {
+    let mut archive =
+        zip::ZipArchive::new(reader).map_err(|e| format!("Failed to read zip archive: {}", e))?;
+
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        // Raw access only reads the metadata, nothing is decompressed
+        let zip_entry = archive
+            .by_index_raw(i)
+            .map_err(|e| format!("Failed to read zip entry: {}", e))?;
+        let is_dir = zip_entry.is_dir();
+
+        entries.push(FlatFsEntry {
+            path: PathBuf::from(zip_entry.name()),
+            entry_type: if is_dir {
+                FsEntryType::Folder
+            } else {
+                FsEntryType::File
+            },
+            size: (!is_dir).then(|| zip_entry.size()),
+            compressed_size: (!is_dir).then(|| zip_entry.compressed_size()),
+            modified: zip_entry.last_modified().and_then(|dt| {
+                NaiveDate::from_ymd_opt(dt.year() as i32, dt.month() as u32, dt.day() as u32)?
+                    .and_hms_opt(dt.hour() as u32, dt.minute() as u32, dt.second() as u32)
+                    .map(|dt| dt.and_utc().timestamp())
+            }),
+            ..Default::default()
+        });
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+
+    /// A tar archive with a folder, a file in it and a symlink
+    fn tar_bytes() -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Directory);
+        header.set_size(0);
+        header.set_mode(0o755);
+        builder
+            .append_data(&mut header, "pkg/", std::io::empty())
+            .unwrap();
+
+        let content = b"fn main() {}\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(1_700_000_000);
+        builder
+            .append_data(&mut header, "pkg/main.rs", &content[..])
+            .unwrap();
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        builder
+            .append_link(&mut header, "pkg/link.rs", "main.rs")
+            .unwrap();
+        builder.into_inner().unwrap()
+    }
+
+    fn read(name: &str, bytes: Vec<u8>) -> Vec<FlatFsEntry> {
+        let kind = ArchiveKind::from_path(Path::new(name)).unwrap();
+        read_entries(kind, Cursor::new(bytes)).unwrap()
+    }
+
+    fn assert_tar_entries(entries: &[FlatFsEntry]) {
+        let paths: Vec<&Path> = entries.iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            [
+                Path::new("pkg"),
+                Path::new("pkg/main.rs"),
+                Path::new("pkg/link.rs")
+            ]
+        );
+        // A single top level folder is kept as it is, not stripped
+        assert!(matches!(entries[0].entry_type, FsEntryType::Folder));
+        assert_eq!(entries[0].size, None);
+        assert_eq!(entries[1].size, Some(13));
+        assert_eq!(entries[1].modified, Some(1_700_000_000));
+        assert_eq!(entries[2].link_target.as_deref(), Some("main.rs"));
+    }
+
+    #[test]
+    fn reads_plain_and_compressed_tar_archives() {
+        assert_tar_entries(&read("pkg.tar", tar_bytes()));
+
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz.write_all(&tar_bytes()).unwrap();
+        assert_tar_entries(&read("pkg.tgz", gz.finish().unwrap()));
+
+        let mut xz = xz2::write::XzEncoder::new(Vec::new(), 6);
+        xz.write_all(&tar_bytes()).unwrap();
+        assert_tar_entries(&read("pkg.tar.xz", xz.finish().unwrap()));
+
+        let zst = zstd::stream::encode_all(&tar_bytes()[..], 0).unwrap();
+        assert_tar_entries(&read("pkg.tar.zst", zst));
+    }
+
+    #[test]
+    fn reads_zip_archives_with_compressed_sizes() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        writer.add_directory("lib/", options).unwrap();
+        writer.start_file("lib/data.txt", options).unwrap();
+        writer.write_all(&[b'a'; 1000]).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let entries = read("lib.jar", bytes);
+        assert_eq!(entries.len(), 2);
+        assert!(matches!(entries[0].entry_type, FsEntryType::Folder));
+        assert_eq!(entries[0].compressed_size, None);
+        assert_eq!(entries[1].path, Path::new("lib/data.txt"));
+        assert_eq!(entries[1].size, Some(1000));
+        assert!(entries[1].compressed_size.is_some_and(|size| size < 1000));
+    }
+
+    #[test]
+    fn rejects_unsupported_extensions() {
+        assert!(ArchiveKind::from_path(Path::new("pkg.rar")).is_none());
+        assert!(ArchiveKind::from_path(Path::new("PKG.TAR.GZ")).is_some());
+        let error = read_archive(Path::new("pkg.rar")).unwrap_err();
+        assert!(error.starts_with("Unsupported archive"), "{}", error);
+        assert!(!is_archive(Path::new("Cargo.toml")));
+    }
+
+    #[test]
+    fn rejects_corrupt_archives() {
+        let kind = ArchiveKind::from_path(Path::new("pkg.zip")).unwrap();
+        assert!(read_entries(kind, Cursor::new(b"not a zip".to_vec())).is_err());
+    }
+}
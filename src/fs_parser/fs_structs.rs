@@ -1,12 +1,142 @@
-#[derive(Debug, Clone, Copy)]
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FsEntryType {
+    #[default]
     File,
     Folder,
+    /// Git submodule, read from a revision with `--git-rev`
+    Submodule,
+}
+
+/// How an entry is rendered with regard to `--highlight`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Highlight {
+    #[default]
+    None,
+    /// Entry matches a highlight pattern
+    Match,
+    /// Entry is an ancestor of a matching entry
+    Path,
+    /// Entry is unrelated to any match and rendered at reduced opacity
+    Dimmed,
+}
+
+/// How an entry changed between the two sides of `glimmr diff`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffStatus {
+    Unchanged,
+    Added,
+    Removed,
+    /// Content changed, or for folders, something inside changed
+    Modified,
+    /// Moved here from the given path
+    Renamed(PathBuf),
+}
+
+/// Working tree status of an entry. Ordered by importance, so a folder takes the highest status
+/// of its descendants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitStatus {
+    Untracked,
+    Staged,
+    Modified,
+    Conflicted,
+}
+
+/// Covered and coverable lines of an entry, summed up for folders
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Coverage {
+    pub covered: u64,
+    pub total: u64,
 }
 
-#[derive(Debug, Clone)]
+impl Coverage {
+    /// Share of covered lines between 0 and 1, if there are any lines to cover
+    pub fn ratio(&self) -> Option<f64> {
+        (self.total > 0).then(|| self.covered as f64 / self.total as f64)
+    }
+}
+
+/// Number of diagnostics of an entry by severity, summed up for folders
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    pub errors: u64,
+    pub warnings: u64,
+    /// Notes, help messages and everything else below a warning
+    pub notes: u64,
+}
+
+/// Lines of an entry by kind, summed up for folders
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LineCounts {
+    pub code: u64,
+    pub comment: u64,
+    pub blank: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct FlatFsEntry {
     pub name: String,
+    #[serde(rename = "type")]
     pub entry_type: FsEntryType,
     pub depth: u32,
+    /// Path relative to the visualized root
+    pub path: PathBuf,
+    /// Folder whose children were left out because they are not on a focus path
+    #[serde(skip_serializing_if = "is_false")]
+    pub hides_children: bool,
+    /// Folder at the depth limit, whose children were not read. Diffs against a snapshot don't
+    /// look below it on either side.
+    #[serde(skip_serializing_if = "is_false")]
+    pub depth_limited: bool,
+    #[serde(skip)]
+    pub highlight: Highlight,
+    /// Entry on a focus path of Miller columns, as the focus or a folder leading to it
+    #[serde(skip)]
+    pub selected: bool,
+    /// Description rendered next to the name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+    /// Size in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Size in bytes within a compressed archive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compressed_size: Option<u64>,
+    /// Last modification time as unix timestamp
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<i64>,
+    /// Target of a symbolic link, or the commit of a submodule
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_target: Option<String>,
+    /// Git blob id of the content, used to detect modifications and renames
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<DiffStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_status: Option<GitStatus>,
+    /// Commits or changed lines in the history of this path, summed up for folders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub churn: Option<u64>,
+    /// Owners from CODEOWNERS. Empty for unowned entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owners: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<Coverage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<Diagnostics>,
+    /// Line counts of text files, summed up for folders
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<LineCounts>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
 }
@@ -0,0 +1,192 @@
+use super::git_rev::repo_relative_path;
+use chrono::{Local, NaiveDate, TimeZone};
+use git2::{Commit, Diff, ObjectType, Patch, Repository, Sort, TreeWalkMode, TreeWalkResult};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Counts how often each path changed in the history of the repository containing `path`.
+/// Paths are relative to `path`, files outside of it are left out.
+///
+/// With `count_lines`, added and removed lines are counted instead of commits. Merge commits
+/// are skipped. `since` is a unix timestamp, `range` a revision (`v1.0`) or range (`v1.0..v2.0`).
+/// Without a range, the history of `HEAD` is walked.
+pub fn read_churn(
+    path: &Path,
+    count_lines: bool,
+    since: Option<i64>,
+    range: Option<&str>,
+) -> Result<HashMap<PathBuf, u64>, String> {
+    let repo = Repository::discover(path)
+        .map_err(|e| format!("Failed to open git repository: {}", e.message()))?;
+    let subdir = repo_relative_path(&repo, path)?;
+
+    let mut churn = HashMap::new();
+    walk_history(&repo, since, range, |_, diff| {
+        for (i, delta) in diff.deltas().enumerate() {
+            let Some(file) = delta.new_file().path().or(delta.old_file().path()) else {
+                continue;
+            };
+            let Ok(rel_path) = file.strip_prefix(&subdir) else {
+                continue;
+            };
+            let amount = if count_lines {
+                match Patch::from_diff(diff, i) {
+                    Ok(Some(patch)) => patch
+                        .line_stats()
+                        .map(|(_, added, removed)| (added + removed) as u64)
+                        .unwrap_or(0),
+                    _ => 0,
+                }
+            } else {
+                1
+            };
+            *churn.entry(rel_path.to_path_buf()).or_insert(0) += amount;
+        }
+        true
+    })?;
+    Ok(churn)
+}
+
+/// Finds the date of the last commit that touched each file in the repository containing
+/// `path`, as unix timestamp. Paths are relative to `path`, files outside of it are left out.
+///
+/// The history is walked from `rev`, or from `HEAD` without one.
+pub fn read_last_changed(path: &Path, rev: Option<&str>) -> Result<HashMap<PathBuf, i64>, String> {
+    let repo = Repository::discover(path)
+        .map_err(|e| format!("Failed to open git repository: {}", e.message()))?;
+    let subdir = repo_relative_path(&repo, path)?;
+
+    // Only files of the revision need a date. Once all have one, older history can't change
+    // anything.
+    let mut tree = match rev {
+        Some(rev) => repo
+            .revparse_single(rev)
+            .and_then(|object| object.peel_to_tree())
+            .map_err(|e| format!("Failed to resolve revision '{}': {}", rev, e.message()))?,
+        None => repo
+            .head()
+            .and_then(|head| head.peel_to_tree())
+            .map_err(|e| format!("Failed to read HEAD: {}", e.message()))?,
+    };
+    if !subdir.as_os_str().is_empty() {
+        tree = tree
+            .get_path(&subdir)
+            .and_then(|entry| entry.to_object(&repo))
+            .and_then(|object| object.peel_to_tree())
+            .map_err(|e| format!("'{}' is not tracked: {}", subdir.display(), e.message()))?;
+    }
+    let mut undated = HashSet::new();
+    tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+        if entry.kind() == Some(ObjectType::Blob)
+            && let Some(name) = entry.name()
+        {
+            undated.insert(PathBuf::from(dir).join(name));
+        }
+        TreeWalkResult::Ok
+    })
+    .map_err(|e| format!("Failed to read tree: {}", e.message()))?;
+
+    let mut last_changed = HashMap::new();
+    walk_history(&repo, None, rev, |commit, diff| {
+        for delta in diff.deltas() {
+            let Some(file) = delta.new_file().path() else {
+                continue;
+            };
+            let Ok(rel_path) = file.strip_prefix(&subdir) else {
+                continue;
+            };
+            if undated.remove(rel_path) {
+                last_changed.insert(rel_path.to_path_buf(), commit.time().seconds());
+            }
+        }
+        !undated.is_empty()
+    })?;
+    Ok(last_changed)
+}
+
+/// Walks the non-merge commits of `range` (or `HEAD`) newest first, calling `visit` with each
+/// commit and its diff against its parent. The walk stops early when `visit` returns false.
+fn walk_history(
+    repo: &Repository,
+    since: Option<i64>,
+    range: Option<&str>,
+    mut visit: impl FnMut(&Commit, &Diff) -> bool,
+) -> Result<(), String> {
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Failed to walk history: {}", e.message()))?;
+    revwalk
+        .set_sorting(Sort::TIME)
+        .map_err(|e| format!("Failed to walk history: {}", e.message()))?;
+    match range {
+        Some(range) if range.contains("..") => revwalk.push_range(range),
+        Some(rev) => repo
+            .revparse_single(rev)
+            .and_then(|object| revwalk.push(object.id())),
+        None => revwalk.push_head(),
+    }
+    .map_err(|e| format!("Failed to resolve revision range: {}", e.message()))?;
+
+    for oid in revwalk {
+        let commit = oid
+            .and_then(|oid| repo.find_commit(oid))
+            .map_err(|e| format!("Failed to read commit: {}", e.message()))?;
+        // Commits are sorted newest first, so everything after this one is older as well
+        if since.is_some_and(|since| commit.time().seconds() < since) {
+            break;
+        }
+        if commit.parent_count() > 1 {
+            continue;
+        }
+
+        let tree = commit
+            .tree()
+            .map_err(|e| format!("Failed to read tree: {}", e.message()))?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(
+                parent
+                    .tree()
+                    .map_err(|e| format!("Failed to read tree: {}", e.message()))?,
+            ),
+            Err(_) => None,
+        };
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(|e| format!("Failed to diff commit {}: {}", commit.id(), e.message()))?;
+        if !visit(&commit, &diff) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Parses a `--since` value: a date (`2024-01-31`) or a duration before now (`90d`, `12w`,
+/// `6m`, `1y`). Returns a unix timestamp.
+pub fn parse_since(s: &str) -> Result<i64, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+        return Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .map(|time| time.timestamp())
+            .ok_or_else(|| format!("'{}' is not a valid local date", s));
+    }
+
+    let invalid = || {
+        format!(
+            "'{}' is neither a date like 2024-01-31 nor a duration like 6m",
+            s
+        )
+    };
+    let (split, _) = s.char_indices().last().ok_or_else(invalid)?;
+    let (amount, unit) = s.split_at(split);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let days = match unit {
+        "d" => amount,
+        "w" => amount * 7,
+        "m" => amount * 30,
+        "y" => amount * 365,
+        _ => return Err(invalid()),
+    };
+    Ok(Local::now().timestamp() - days * 24 * 60 * 60)
+}
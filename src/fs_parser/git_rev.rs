@@ -0,0 +1,186 @@
+use super::fs_structs::{FlatFsEntry, FsEntryType};
+use git2::{ObjectType, Repository, TreeWalkMode, TreeWalkResult};
+use std::path::{Path, PathBuf};
+
+/// Reads the tree of a git revision from the repository containing `path`, without checking
+/// it out. Only the local repository is accessed.
+///
+/// If `path` is a subdirectory of the repository, only that subdirectory is read. Entries are
+/// relative to it. Submodules are returned as `FsEntryType::Submodule` with the commit they
+/// point to as `link_target`. Entries more than `max_depth` folders deep are not read, so large
+/// repositories don't have to be read as a whole.
+pub fn read_git_tree(path: &Path, rev: &str, max_depth: u32) -> Result<Vec<FlatFsEntry>, String> {
+    let repo = Repository::discover(path)
+        .map_err(|e| format!("Failed to open git repository: {}", e.message()))?;
+    let commit = repo
+        .revparse_single(rev)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|e| format!("Failed to resolve revision '{}': {}", rev, e.message()))?;
+    let mut tree = commit
+        .tree()
+        .map_err(|e| format!("Failed to read tree: {}", e.message()))?;
+
+    // Descend to the subdirectory the path points at
+    let subdir = repo_relative_path(&repo, path)?;
+    if !subdir.as_os_str().is_empty() {
+        tree = tree
+            .get_path(&subdir)
+            .and_then(|entry| entry.to_object(&repo))
+            .and_then(|object| object.peel_to_tree())
+            .map_err(|e| {
+                format!(
+                    "'{}' is not a folder at '{}': {}",
+                    subdir.display(),
+                    rev,
+                    e.message()
+                )
+            })?;
+    }
+
+    let odb = repo
+        .odb()
+        .map_err(|e| format!("Failed to open object database: {}", e.message()))?;
+
+    let mut entries = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |dir, tree_entry| {
+        let Some(name) = tree_entry.name() else {
+            return TreeWalkResult::Ok;
+        };
+        let path = PathBuf::from(dir).join(name);
+        let depth = Path::new(dir).components().count() as u32;
+        if depth > max_depth {
+            return TreeWalkResult::Skip;
+        }
+
+        let entry = match tree_entry.kind() {
+            Some(ObjectType::Tree) => FlatFsEntry {
+                path,
+                entry_type: FsEntryType::Folder,
+                ..Default::default()
+            },
+            // Gitlinks point to the commit of a submodule
+            Some(ObjectType::Commit) => FlatFsEntry {
+                path,
+                entry_type: FsEntryType::Submodule,
+                link_target: Some(tree_entry.id().to_string()[..7].to_string()),
+                ..Default::default()
+            },
+            Some(ObjectType::Blob) => {
+                let is_symlink = tree_entry.filemode() == 0o120000;
+                FlatFsEntry {
+                    path,
+                    entry_type: FsEntryType::File,
+                    size: odb
+                        .read_header(tree_entry.id())
+                        .ok()
+                        .map(|(size, _)| size as u64),
+                    link_target: is_symlink
+                        .then(|| repo.find_blob(tree_entry.id()).ok())
+                        .flatten()
+                        .map(|blob| String::from_utf8_lossy(blob.content()).into_owned()),
+                    content_hash: Some(tree_entry.id().to_string()),
+                    ..Default::default()
+                }
+            }
+            _ => return TreeWalkResult::Ok,
+        };
+        entries.push(entry);
+        if depth == max_depth {
+            TreeWalkResult::Skip
+        } else {
+            TreeWalkResult::Ok
+        }
+    })
+    .map_err(|e| format!("Failed to walk tree: {}", e.message()))?;
+
+    Ok(entries)
+}
+
+/// Returns `path` relative to the working directory of `repo`
+pub fn repo_relative_path(repo: &Repository, path: &Path) -> Result<PathBuf, String> {
+    // Bare repositories have no working directory to be relative to
+    let Some(workdir) = repo.workdir() else {
+        return Ok(PathBuf::new());
+    };
+    let workdir = workdir
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve repository path: {}", e))?;
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{}': {}", path.display(), e))?;
+    Ok(path
+        .strip_prefix(&workdir)
+        .map(Path::to_path_buf)
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use std::fs;
+
+    /// Commits `files` to a new repository in a temporary folder, together with a submodule
+    /// `sub`. Returns the folder of the repository.
+    fn committed_repo(name: &str, files: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("glimmr-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let repo = Repository::init(&dir).unwrap();
+        for file in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, file).unwrap();
+        }
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+
+        // Gitlinks point at a commit of another repository, which doesn't need to exist here
+        let mut builder = repo.treebuilder(Some(&tree)).unwrap();
+        let target = git2::Oid::from_str("1234567890abcdef1234567890abcdef12345678").unwrap();
+        builder.insert("sub", target, 0o160000).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+
+        let signature = Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        dir
+    }
+
+    #[test]
+    fn entries_below_the_depth_limit_are_skipped() {
+        let dir = committed_repo("git-rev-depth", &["a/b/c.txt", "a/d.txt", "e.txt"]);
+        let entries = read_git_tree(&dir, "HEAD", 1).unwrap();
+        let paths: Vec<&Path> = entries.iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            ["a", "a/b", "a/d.txt", "e.txt", "sub"].map(Path::new)
+        );
+
+        let folder = &entries[1];
+        assert!(matches!(folder.entry_type, FsEntryType::Folder));
+        let file = &entries[2];
+        assert_eq!(file.size, Some("a/d.txt".len() as u64));
+        assert!(file.content_hash.is_some());
+
+        let submodule = &entries[4];
+        assert!(matches!(submodule.entry_type, FsEntryType::Submodule));
+        assert_eq!(submodule.link_target.as_deref(), Some("1234567"));
+
+        let entries = read_git_tree(&dir, "HEAD", 0).unwrap();
+        assert_eq!(entries.len(), 3);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn subdirectories_are_read_relative_to_themselves() {
+        let dir = committed_repo("git-rev-subdir", &["a/b/c.txt", "e.txt"]);
+        let entries = read_git_tree(&dir.join("a"), "HEAD", u32::MAX).unwrap();
+        let paths: Vec<&Path> = entries.iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(paths, ["b", "b/c.txt"].map(Path::new));
+        assert!(read_git_tree(&dir, "no-such-rev", 1).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
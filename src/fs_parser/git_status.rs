@@ -0,0 +1,112 @@
+use super::fs_structs::GitStatus;
+use super::git_rev::repo_relative_path;
+use git2::{Repository, Status, StatusOptions};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Reads the working tree status of the repository containing `path`. Paths are relative to
+/// `path`, files outside of it are left out.
+///
+/// Only changed files are returned. Untracked folders are expanded into their files, so folders
+/// can aggregate the status of their contents later.
+pub fn read_git_status(path: &Path) -> Result<HashMap<PathBuf, GitStatus>, String> {
+    let repo = Repository::discover(path)
+        .map_err(|e| format!("Failed to open git repository: {}", e.message()))?;
+    if repo.is_bare() {
+        return Err("Bare repositories have no working tree".to_string());
+    }
+    let subdir = repo_relative_path(&repo, path)?;
+
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+    let statuses = repo
+        .statuses(Some(&mut options))
+        .map_err(|e| format!("Failed to read git status: {}", e.message()))?;
+
+    let mut result = HashMap::new();
+    for status_entry in statuses.iter() {
+        let Some(repo_path) = status_entry.path() else {
+            continue;
+        };
+        let Ok(rel_path) = Path::new(repo_path).strip_prefix(&subdir) else {
+            continue;
+        };
+        if let Some(status) = to_git_status(status_entry.status()) {
+            result.insert(rel_path.to_path_buf(), status);
+        }
+    }
+    Ok(result)
+}
+
+/// Maps the git2 status flags to the most important status they contain
+fn to_git_status(status: Status) -> Option<GitStatus> {
+    if status.is_conflicted() {
+        Some(GitStatus::Conflicted)
+    } else if status.intersects(
+        Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_TYPECHANGE | Status::WT_RENAMED,
+    ) {
+        Some(GitStatus::Modified)
+    } else if status.intersects(
+        Status::INDEX_NEW
+            | Status::INDEX_MODIFIED
+            | Status::INDEX_DELETED
+            | Status::INDEX_RENAMED
+            | Status::INDEX_TYPECHANGE,
+    ) {
+        Some(GitStatus::Staged)
+    } else if status.is_wt_new() {
+        Some(GitStatus::Untracked)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{IndexAddOption, Signature};
+    use std::fs;
+
+    #[test]
+    fn changes_are_read_relative_to_the_path() {
+        let dir = std::env::temp_dir().join(format!("glimmr-git-status-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let repo = Repository::init(&dir).unwrap();
+        for file in ["src/main.rs", "src/lib.rs", "README.md"] {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, file).unwrap();
+        }
+        let mut index = repo.index().unwrap();
+        index.add_all(["*"], IndexAddOption::DEFAULT, None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+
+        fs::write(dir.join("src/main.rs"), "changed").unwrap();
+        fs::write(dir.join("src/staged.rs"), "new").unwrap();
+        index.add_path(Path::new("src/staged.rs")).unwrap();
+        index.write().unwrap();
+        fs::create_dir_all(dir.join("src/new")).unwrap();
+        fs::write(dir.join("src/new/a.rs"), "untracked").unwrap();
+        fs::write(dir.join("README.md"), "changed outside").unwrap();
+
+        let statuses = read_git_status(&dir.join("src")).unwrap();
+        let mut statuses: Vec<(PathBuf, GitStatus)> = statuses.into_iter().collect();
+        statuses.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            statuses,
+            [
+                (PathBuf::from("main.rs"), GitStatus::Modified),
+                (PathBuf::from("new/a.rs"), GitStatus::Untracked),
+                (PathBuf::from("staged.rs"), GitStatus::Staged),
+            ]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
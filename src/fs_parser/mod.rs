@@ -1,7 +1,19 @@
+pub mod archive;
 pub mod fs_structs;
+pub mod git_history;
+pub mod git_rev;
+pub mod git_status;
+pub mod path_list;
+pub mod snapshot;
+pub mod synthetic;
+pub mod tree_dsl;
+pub mod tree_import;
 use fs_structs::{FlatFsEntry, FsEntryType};
 use ignore::{WalkBuilder, overrides::OverrideBuilder};
-use std::path::PathBuf;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
 /// Parses the filesystem starting from `path` and returns a flat vector of `FlatFsEntry`.
 ///
@@ -17,6 +29,8 @@ use std::path::PathBuf;
 /// * `excludes` - A vector of `PathBuf`s to exclude from the traversal. These are treated as glob patterns.
 /// * `use_gitignore` - Whether to respect `.gitignore` files for exclusion.
 /// * `ignore_hidden` - Whether to ignore hidden files and directories.
+/// * `focus` - Paths to expand. If not empty, only the focused subtrees are expanded (up to
+///   `max_depth` levels below each focus path) and siblings along the way are shown collapsed.
 pub fn parse_fs_flat(
     path: PathBuf,
     max_depth: u32,
@@ -24,6 +38,7 @@ pub fn parse_fs_flat(
     excludes: Vec<PathBuf>,
     use_gitignore: bool,
     ignore_hidden: bool,
+    focus: Vec<PathBuf>,
 ) -> Vec<FlatFsEntry> {
     let mut flat_entries = Vec::new();
 
@@ -54,7 +69,20 @@ pub fn parse_fs_flat(
     } else {
         (max_depth + 1) as usize
     };
-    walk_builder.max_depth(Some(final_max_depth));
+    let focus = normalize_focus_paths(&path, focus);
+    if focus.is_empty() {
+        walk_builder.max_depth(Some(final_max_depth));
+    } else {
+        // Only descend into focus paths and the folders leading to them, and no deeper than
+        // `max_depth` below a focus path.
+        let root = path.clone();
+        let filter_focus = focus.clone();
+        walk_builder.filter_entry(move |entry| {
+            let rel_path = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+            is_on_focus_path(rel_path, &filter_focus)
+                && focus_depth(rel_path, &filter_focus).is_none_or(|depth| depth <= max_depth)
+        });
+    }
 
     // Build and iterate over the directory walker.
     for result in walk_builder.build() {
@@ -71,25 +99,51 @@ pub fn parse_fs_flat(
                     depth -= 1;
                 }
 
-                // Skip entries that are deeper than the specified max_depth.
-                if depth > max_depth {
-                    continue;
-                }
-
                 // Determine the entry type (File or Folder).
-                let entry_type = if entry.file_type().map_or(false, |ft| ft.is_dir()) {
+                let entry_type = if entry.file_type().is_some_and(|ft| ft.is_dir()) {
                     FsEntryType::Folder
                 } else {
                     FsEntryType::File
                 };
 
+                let rel_path = entry.path().strip_prefix(&path).unwrap_or(entry.path());
+
+                // Skip entries that are deeper than the specified max_depth. With focus paths,
+                // the walk is already cut off below them.
+                if focus.is_empty() && depth > max_depth {
+                    continue;
+                }
+                // Folders next to a focus path are shown, but not expanded.
+                let is_folder = matches!(entry_type, FsEntryType::Folder);
+                let entry_focus_depth = focus_depth(rel_path, &focus);
+                let hides_children = !focus.is_empty()
+                    && is_folder
+                    && entry_focus_depth.is_none()
+                    && !focus.iter().any(|f| f.starts_with(rel_path));
+                let depth_limited = is_folder
+                    && match entry_focus_depth {
+                        Some(focus_depth) => focus_depth == max_depth,
+                        None => focus.is_empty() && depth == max_depth,
+                    };
+
                 let name = entry.file_name().to_string_lossy().into_owned();
+                let modified = entry
+                    .metadata()
+                    .ok()
+                    .and_then(|metadata| metadata.modified().ok())
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .map(|duration| duration.as_secs() as i64);
 
                 // Add the entry to our flat list.
                 flat_entries.push(FlatFsEntry {
                     name,
                     entry_type,
                     depth,
+                    path: rel_path.to_path_buf(),
+                    hides_children,
+                    depth_limited,
+                    modified,
+                    ..Default::default()
                 });
             }
             Err(err) => eprintln!("ERROR: {}", err),
@@ -98,3 +152,68 @@ pub fn parse_fs_flat(
 
     flat_entries
 }
+
+/// Makes focus paths relative to the walked root. Paths that already start with the root are
+/// stripped, all others are treated as relative to it.
+pub fn normalize_focus_paths(root: &Path, focus: Vec<PathBuf>) -> Vec<PathBuf> {
+    focus
+        .into_iter()
+        .map(|f| match f.strip_prefix(root) {
+            Ok(rel) => rel.to_path_buf(),
+            Err(_) => f,
+        })
+        .map(|f| {
+            f.components()
+                .filter(|c| !matches!(c, Component::CurDir))
+                .collect::<PathBuf>()
+        })
+        .collect()
+}
+
+/// Returns whether an entry (relative to the root) should be visited when focus paths are set.
+/// That is the case for entries inside a focus path, and for entries whose parent leads to one.
+fn is_on_focus_path(rel_path: &Path, focus: &[PathBuf]) -> bool {
+    let parent = rel_path.parent().unwrap_or(Path::new(""));
+    focus
+        .iter()
+        .any(|f| f.starts_with(parent) || rel_path.starts_with(f))
+}
+
+/// Returns the depth of an entry below the focus path it lies in, or `None` if it is not inside
+/// any focus path. The focus path itself has depth 0.
+fn focus_depth(rel_path: &Path, focus: &[PathBuf]) -> Option<u32> {
+    focus
+        .iter()
+        .filter(|f| rel_path.starts_with(f))
+        .map(|f| (rel_path.components().count() - f.components().count()) as u32)
+        .min()
+}
+
+/// Reads the content of a file, or of stdin if `source` is `-`
+pub fn read_source(source: &Path) -> Result<String, String> {
+    if source == Path::new("-") {
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Failed to read stdin: {}", e))?;
+        Ok(content)
+    } else {
+        fs::read_to_string(source).map_err(|e| format!("Failed to read file: {}", e))
+    }
+}
+
+/// Finds the file below `root` a path from a report refers to, and returns it relative to
+/// `root`. Paths that don't point at an existing file are matched by their longest suffix that
+/// does, so reports created in CI or for a parent folder still line up.
+pub fn resolve_report_path(report_path: &Path, root: &Path) -> Option<PathBuf> {
+    if let Ok(rel_path) = report_path.strip_prefix(root) {
+        return Some(rel_path.to_path_buf());
+    }
+    let components: Vec<Component> = report_path
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect();
+    (0..components.len())
+        .map(|skip| components[skip..].iter().collect::<PathBuf>())
+        .find(|suffix| root.join(suffix).is_file())
+}
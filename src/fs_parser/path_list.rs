@@ -0,0 +1,27 @@
+use super::fs_structs::{FlatFsEntry, FsEntryType};
+use super::read_source;
+use std::path::{Path, PathBuf};
+
+/// Reads a list of relative paths, as printed by `git ls-files`, `find` or `fd`.
+///
+/// The list is read from stdin if `source` is `-`. Paths may be separated by newlines or NUL
+/// characters (`-z`, `-print0`, `-0`). Paths with a trailing `/` are treated as folders.
+pub fn read_path_list(source: &Path) -> Result<Vec<FlatFsEntry>, String> {
+    let content = read_source(source)?;
+    let separator = if content.contains('\0') { '\0' } else { '\n' };
+
+    Ok(content
+        .split(separator)
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.is_empty())
+        .map(|line| FlatFsEntry {
+            path: PathBuf::from(line),
+            entry_type: if line.ends_with('/') {
+                FsEntryType::Folder
+            } else {
+                FsEntryType::File
+            },
+            ..Default::default()
+        })
+        .collect())
+}
@@ -0,0 +1,9 @@
+use super::fs_structs::FlatFsEntry;
+use super::read_source;
+use std::path::Path;
+
+/// Reads a snapshot written with `--filetype json`
+pub fn read_snapshot(source: &Path) -> Result<Vec<FlatFsEntry>, String> {
+    let content = read_source(source)?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse snapshot: {}", e))
+}
@@ -0,0 +1,346 @@
+use super::fs_structs::{FlatFsEntry, FsEntryType};
+use super::{focus_depth, is_on_focus_path};
+use ignore::overrides::{Override, OverrideBuilder};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Component, Path, PathBuf};
+
+/// Node of a filestructure that is assembled from a set of paths instead of a filesystem walk
+#[derive(Default)]
+struct SyntheticNode {
+    entry: Option<FlatFsEntry>,
+    is_folder: bool,
+    /// Position among the siblings, in the order the entries were given
+    order: usize,
+    children: BTreeMap<OsString, SyntheticNode>,
+}
+
+/// Which entries of a synthetic filestructure are shown, with the same semantics as for the
+/// filesystem walk
+#[derive(Debug, Clone, Default)]
+pub struct SyntheticFilters {
+    /// The maximum depth, relative to the root
+    pub max_depth: u32,
+    /// Whether to include the root itself in the output
+    pub include_root: bool,
+    /// Glob patterns of entries to leave out
+    pub excludes: Vec<PathBuf>,
+    /// Whether to ignore hidden files and directories
+    pub ignore_hidden: bool,
+    /// Paths to expand, relative to the root. If not empty, only the focused subtrees are
+    /// expanded (up to `max_depth` levels below each focus path) and siblings along the way
+    /// are shown collapsed.
+    pub focus: Vec<PathBuf>,
+}
+
+/// Builds the flat filestructure from entries that don't come from walking the filesystem,
+/// e.g. a list of paths or the contents of an archive.
+///
+/// Each entry only needs its `path` (relative to the root) and `entry_type` set, any other
+/// metadata is kept. Folders that are only implied by the paths of their children are
+/// synthesized. Entries are sorted by name, unless `sort_by_name` is unset, in which case the
+/// order in which they were given is kept.
+///
+/// # Arguments
+///
+/// * `root_name` - Name of the root entry, shown when `include_root` is set.
+/// * `entries` - The entries, in any order.
+/// * `filters` - Which entries are shown.
+/// * `sort_by_name` - Whether to sort siblings by name.
+pub fn flatten_synthetic(
+    root_name: &str,
+    entries: Vec<FlatFsEntry>,
+    filters: &SyntheticFilters,
+    sort_by_name: bool,
+) -> Vec<FlatFsEntry> {
+    let mut root = SyntheticNode {
+        is_folder: true,
+        ..Default::default()
+    };
+
+    for entry in entries {
+        let path = normalize_synthetic_path(&entry.path);
+        let mut node = &mut root;
+        for component in path.iter() {
+            // Anything that has children is a folder
+            node.is_folder = true;
+            let order = node.children.len();
+            node = node
+                .children
+                .entry(component.to_os_string())
+                .or_insert_with(|| SyntheticNode {
+                    order,
+                    ..Default::default()
+                });
+        }
+        if matches!(entry.entry_type, FsEntryType::Folder) {
+            node.is_folder = true;
+        }
+        node.entry = Some(FlatFsEntry { path, ..entry });
+    }
+
+    // Exclusion patterns are matched relative to the root, like for the filesystem walk
+    let mut override_builder = OverrideBuilder::new("");
+    for exclude in &filters.excludes {
+        if let Err(e) = override_builder.add(&exclude.to_string_lossy()) {
+            eprintln!("Could not add exclude pattern: {}", e);
+        }
+    }
+    let overrides = override_builder.build().unwrap_or_else(|e| {
+        eprintln!("Could not build exclude patterns: {}", e);
+        Override::empty()
+    });
+
+    let mut flat_entries = Vec::new();
+    let focus: Vec<PathBuf> = filters
+        .focus
+        .iter()
+        .map(|f| normalize_synthetic_path(f))
+        .collect();
+    let options = FlattenOptions {
+        max_depth: filters.max_depth,
+        overrides: &overrides,
+        ignore_hidden: filters.ignore_hidden,
+        focus: &focus,
+        sort_by_name,
+    };
+    if filters.include_root {
+        flat_entries.push(FlatFsEntry {
+            name: root_name.to_string(),
+            entry_type: FsEntryType::Folder,
+            depth: 0,
+            ..root.entry.take().unwrap_or_default()
+        });
+        flatten_children(&root, Path::new(""), 1, &options, &mut flat_entries);
+    } else {
+        flatten_children(&root, Path::new(""), 0, &options, &mut flat_entries);
+    }
+
+    flat_entries
+}
+
+struct FlattenOptions<'a> {
+    max_depth: u32,
+    overrides: &'a Override,
+    ignore_hidden: bool,
+    focus: &'a [PathBuf],
+    sort_by_name: bool,
+}
+
+fn flatten_children(
+    node: &SyntheticNode,
+    path: &Path,
+    depth: u32,
+    options: &FlattenOptions,
+    flat_entries: &mut Vec<FlatFsEntry>,
+) {
+    // With focus paths, the depth is counted from the focus path an entry lies in instead
+    if options.focus.is_empty() && depth > options.max_depth {
+        return;
+    }
+
+    let mut children: Vec<_> = node.children.iter().collect();
+    if !options.sort_by_name {
+        children.sort_by_key(|(_, child)| child.order);
+    }
+
+    for (name, child) in children {
+        let child_path = path.join(name);
+        let name = name.to_string_lossy().into_owned();
+
+        if options.ignore_hidden && name.starts_with('.') {
+            continue;
+        }
+        if options
+            .overrides
+            .matched(&child_path, child.is_folder)
+            .is_ignore()
+        {
+            continue;
+        }
+        let focus = options.focus;
+        if !focus.is_empty()
+            && (!is_on_focus_path(&child_path, focus)
+                || focus_depth(&child_path, focus).is_some_and(|d| d > options.max_depth))
+        {
+            continue;
+        }
+        // Folders next to a focus path are shown, but not expanded
+        let child_focus_depth = focus_depth(&child_path, focus);
+        let hides_children = !focus.is_empty()
+            && child.is_folder
+            && child_focus_depth.is_none()
+            && !focus.iter().any(|f| f.starts_with(&child_path));
+        let depth_limited = child.is_folder
+            && match child_focus_depth {
+                Some(focus_depth) => focus_depth == options.max_depth,
+                None => focus.is_empty() && depth == options.max_depth,
+            };
+
+        let entry_type = match &child.entry {
+            _ if child.is_folder => FsEntryType::Folder,
+            Some(entry) => entry.entry_type,
+            None => FsEntryType::File,
+        };
+        let entry = child.entry.clone().unwrap_or_default();
+        flat_entries.push(FlatFsEntry {
+            name,
+            entry_type,
+            depth,
+            path: child_path.clone(),
+            hides_children: entry.hides_children || hides_children,
+            depth_limited: entry.depth_limited || depth_limited,
+            ..entry
+        });
+
+        if !hides_children {
+            flatten_children(child, &child_path, depth + 1, options, flat_entries);
+        }
+    }
+}
+
+/// Keeps only the normal components, so all paths are relative to the synthetic root
+fn normalize_synthetic_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str) -> FlatFsEntry {
+        FlatFsEntry {
+            path: PathBuf::from(path),
+            entry_type: FsEntryType::File,
+            ..Default::default()
+        }
+    }
+
+    fn filters(max_depth: u32) -> SyntheticFilters {
+        SyntheticFilters {
+            max_depth,
+            ..Default::default()
+        }
+    }
+
+    /// Path and depth of every entry, with the flags the renderers rely on
+    fn shown(entries: &[FlatFsEntry]) -> Vec<(String, u32, bool, bool)> {
+        entries
+            .iter()
+            .map(|e| {
+                (
+                    e.path.display().to_string(),
+                    e.depth,
+                    e.hides_children,
+                    e.depth_limited,
+                )
+            })
+            .collect()
+    }
+
+    fn row(path: &str, depth: u32) -> (String, u32, bool, bool) {
+        (path.to_string(), depth, false, false)
+    }
+
+    #[test]
+    fn implied_folders_are_synthesized_and_sorted() {
+        let entries = vec![file("./b/z.txt"), file("b/a.txt"), file("a.txt")];
+        let flat = flatten_synthetic("root", entries, &filters(5), true);
+        assert_eq!(
+            shown(&flat),
+            [
+                row("a.txt", 0),
+                row("b", 0),
+                row("b/a.txt", 1),
+                row("b/z.txt", 1)
+            ]
+        );
+        assert!(matches!(flat[1].entry_type, FsEntryType::Folder));
+    }
+
+    #[test]
+    fn given_order_is_kept_without_sorting() {
+        let entries = vec![file("b/z.txt"), file("b/a.txt"), file("a.txt")];
+        let flat = flatten_synthetic("root", entries, &filters(5), false);
+        assert_eq!(
+            shown(&flat),
+            [
+                row("b", 0),
+                row("b/z.txt", 1),
+                row("b/a.txt", 1),
+                row("a.txt", 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn folders_at_the_depth_limit_are_marked() {
+        let entries = vec![file("a/b/c.txt"), file("d.txt")];
+        let flat = flatten_synthetic("root", entries, &filters(1), true);
+        assert_eq!(
+            shown(&flat),
+            [
+                row("a", 0),
+                ("a/b".to_string(), 1, false, true),
+                row("d.txt", 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn root_shifts_the_depths() {
+        let entries = vec![file("a/b.txt")];
+        let filters = SyntheticFilters {
+            max_depth: 1,
+            include_root: true,
+            ..Default::default()
+        };
+        let flat = flatten_synthetic("root", entries, &filters, true);
+        assert_eq!(
+            shown(&flat),
+            [row("", 0), ("a".to_string(), 1, false, true)]
+        );
+        assert_eq!(flat[0].name, "root");
+    }
+
+    #[test]
+    fn excluded_and_hidden_entries_are_left_out() {
+        let entries = vec![file(".git/config"), file("target/out"), file("src/main.rs")];
+        let filters = SyntheticFilters {
+            max_depth: 5,
+            excludes: vec![PathBuf::from("!target")],
+            ignore_hidden: true,
+            ..Default::default()
+        };
+        let flat = flatten_synthetic("root", entries, &filters, true);
+        assert_eq!(shown(&flat), [row("src", 0), row("src/main.rs", 1)]);
+    }
+
+    #[test]
+    fn focus_expands_its_path_only() {
+        let entries = vec![
+            file("a/x/deep.txt"),
+            file("a/y.txt"),
+            file("b/c/d/e.txt"),
+            file("top.txt"),
+        ];
+        let filters = SyntheticFilters {
+            max_depth: 1,
+            focus: vec![PathBuf::from("b/c")],
+            ..Default::default()
+        };
+        let flat = flatten_synthetic("root", entries, &filters, true);
+        assert_eq!(
+            shown(&flat),
+            [
+                ("a".to_string(), 0, true, false),
+                row("b", 0),
+                row("b/c", 1),
+                ("b/c/d".to_string(), 2, false, true),
+                row("top.txt", 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use super::fs_structs::{FlatFsEntry, FsEntryType};
+use super::tree_import::ImportedNode;
+
+/// Box-drawing characters `tree` uses to draw the hierarchy
+const BOX_DRAWING_CHARS: &[char] = &['│', '├', '└', '─'];
+/// Indentation steps `tree --charset=ascii` draws the hierarchy with. Unlike box-drawing
+/// characters, these are only matched as a whole, so names can start with `-`, `|` or `` ` ``.
+const ASCII_PREFIXES: &[&str] = &["|-- ", "`-- ", "|   "];
+/// Type markers `tree -F` appends to files, besides the `/` of folders
+const CLASSIFIERS: &[char] = &['*', '@', '|', '='];
+
+/// A line of the tree, split into its parts
+struct Line<'a> {
+    number: usize,
+    indent: usize,
+    name: &'a str,
+    annotation: Option<&'a str>,
+    /// Whether the indentation was drawn by `tree`
+    drawn: bool,
+}
+
+/// Parses a hand-authored, indentation-based tree.
+///
+/// Each line holds one entry, nested entries are indented deeper than their parent. The output
+/// of `tree` can be pasted as is, its box-drawing characters count as indentation. Entries with
+/// a trailing `/` or nested entries are folders, and a `# comment` becomes the annotation. The
+/// type markers of `tree -F` are removed if the tree was drawn by it.
+///
+/// ```text
+/// project/
+///     src/            # the code
+///         main.rs
+///     README.md
+/// ```
+pub fn parse_tree_dsl(content: &str) -> Result<Vec<ImportedNode>, String> {
+    let lines: Vec<Line> = content
+        .lines()
+        .enumerate()
+        .filter_map(|(number, line)| {
+            let (indent, rest, drawn) = split_indentation(line);
+            let (name, annotation) = split_annotation(rest);
+            (!name.is_empty() && !is_tree_summary(name)).then_some(Line {
+                number,
+                indent,
+                name,
+                annotation,
+                drawn,
+            })
+        })
+        .collect();
+    let classified = is_classified(&lines);
+
+    // Stack of (indentation, node) for the ancestors of the current line
+    let mut ancestors: Vec<(usize, ImportedNode)> = Vec::new();
+    let mut top_level: Vec<ImportedNode> = Vec::new();
+
+    for line in &lines {
+        let Line {
+            indent, annotation, ..
+        } = *line;
+        while ancestors.last().is_some_and(|(i, _)| *i >= indent) {
+            let (_, node) = ancestors.pop().unwrap();
+            attach(node, &mut ancestors, &mut top_level);
+        }
+
+        // Strip symlink targets and type markers
+        let (name, link_target) = match line.name.split_once(" -> ") {
+            Some((name, target)) => (name, Some(target.to_string())),
+            None => (line.name, None),
+        };
+        let (name, is_folder) = match name.strip_suffix('/') {
+            Some(name) => (name, true),
+            None if classified => (name.strip_suffix(CLASSIFIERS).unwrap_or(name), false),
+            None => (name, false),
+        };
+        if name.is_empty() {
+            return Err(format!("Line {}: Entry without a name", line.number + 1));
+        }
+
+        let node = ImportedNode {
+            name: name.to_string(),
+            entry: FlatFsEntry {
+                entry_type: if is_folder {
+                    FsEntryType::Folder
+                } else {
+                    FsEntryType::File
+                },
+                annotation: annotation.map(str::to_string),
+                link_target,
+                ..Default::default()
+            },
+            children: Vec::new(),
+        };
+        ancestors.push((indent, node));
+    }
+
+    while let Some((_, node)) = ancestors.pop() {
+        attach(node, &mut ancestors, &mut top_level);
+    }
+
+    Ok(top_level)
+}
+
+/// Adds a finished node to its parent, which is the last remaining ancestor
+fn attach(
+    node: ImportedNode,
+    ancestors: &mut [(usize, ImportedNode)],
+    top_level: &mut Vec<ImportedNode>,
+) {
+    match ancestors.last_mut() {
+        Some((_, parent)) => parent.children.push(node),
+        None => top_level.push(node),
+    }
+}
+
+/// Splits a line into its indentation width and the rest, and tells whether the indentation was
+/// drawn by `tree`. Tabs count as four columns.
+fn split_indentation(line: &str) -> (usize, &str, bool) {
+    let mut indent = 0;
+    let mut drawn = false;
+    let mut rest = line;
+    loop {
+        if let Some(after) = ASCII_PREFIXES.iter().find_map(|p| rest.strip_prefix(p)) {
+            indent += 4;
+            drawn = true;
+            rest = after;
+            continue;
+        }
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some('\t') => indent += 4,
+            Some(' ' | '\u{a0}') => indent += 1,
+            Some(c) if BOX_DRAWING_CHARS.contains(&c) => {
+                indent += 1;
+                drawn = true;
+            }
+            _ => return (indent, rest, drawn),
+        }
+        rest = chars.as_str();
+    }
+}
+
+/// Whether the tree looks like `tree -F` output: it was drawn by `tree`, and every entry with
+/// children is marked as a folder. Plain `tree` output never marks folders.
+fn is_classified(lines: &[Line]) -> bool {
+    let mut parents = lines
+        .windows(2)
+        .filter(|pair| pair[1].indent > pair[0].indent)
+        .map(|pair| pair[0].name.split(" -> ").next().unwrap_or(pair[0].name))
+        .peekable();
+    lines.iter().any(|line| line.drawn)
+        && parents.peek().is_some()
+        && parents.all(|name| name.ends_with('/'))
+}
+
+/// Splits off a `# comment`. A `#` only starts a comment at the beginning or after whitespace.
+fn split_annotation(rest: &str) -> (&str, Option<&str>) {
+    if rest.starts_with('#') {
+        return ("", None);
+    }
+    match rest.find(" #").or_else(|| rest.find("\t#")) {
+        Some(i) => {
+            let annotation = rest[i + 2..].trim();
+            (
+                rest[..i].trim_end(),
+                (!annotation.is_empty()).then_some(annotation),
+            )
+        }
+        None => (rest.trim_end(), None),
+    }
+}
+
+/// Matches the `3 directories, 10 files` summary `tree` prints at the end
+fn is_tree_summary(line: &str) -> bool {
+    let mut words = line.split_whitespace();
+    let is_count = |w: Option<&str>| w.is_some_and(|w| w.parse::<u64>().is_ok());
+    is_count(words.next())
+        && words
+            .next()
+            .is_some_and(|w| w.starts_with("director") || w.starts_with("file"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flattens the parsed nodes into `(depth, name, is_folder)` for easy comparison
+    fn parse(content: &str) -> Vec<(usize, String, bool)> {
+        fn flatten(nodes: &[ImportedNode], depth: usize, out: &mut Vec<(usize, String, bool)>) {
+            for node in nodes {
+                let is_folder = matches!(node.entry.entry_type, FsEntryType::Folder);
+                out.push((depth, node.name.clone(), is_folder));
+                flatten(&node.children, depth + 1, out);
+            }
+        }
+        let mut out = Vec::new();
+        flatten(&parse_tree_dsl(content).unwrap(), 0, &mut out);
+        out
+    }
+
+    fn entry(depth: usize, name: &str, is_folder: bool) -> (usize, String, bool) {
+        (depth, name.to_string(), is_folder)
+    }
+
+    #[test]
+    fn parses_indentation_and_annotations() {
+        let content = "project/\n    src/   # the code\n        main.rs\n    README.md\n";
+        let nodes = parse_tree_dsl(content).unwrap();
+        assert_eq!(nodes.len(), 1);
+        let src = &nodes[0].children[0];
+        assert_eq!(src.name, "src");
+        assert_eq!(src.entry.annotation.as_deref(), Some("the code"));
+        assert_eq!(src.children[0].name, "main.rs");
+        assert_eq!(nodes[0].children[1].name, "README.md");
+    }
+
+    #[test]
+    fn parses_unicode_tree_output() {
+        let content = ".\n├── src\n│\u{a0}\u{a0} ├── lib.rs\n│\u{a0}\u{a0} └── main.rs\n└── Cargo.toml\n\n1 directory, 3 files\n";
+        assert_eq!(
+            parse(content),
+            vec![
+                entry(0, ".", false),
+                entry(1, "src", false),
+                entry(2, "lib.rs", false),
+                entry(2, "main.rs", false),
+                entry(1, "Cargo.toml", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_ascii_tree_output() {
+        let content = ".\n|-- src\n|   `-- main.rs\n`-- Cargo.toml\n";
+        assert_eq!(
+            parse(content),
+            vec![
+                entry(0, ".", false),
+                entry(1, "src", false),
+                entry(2, "main.rs", false),
+                entry(1, "Cargo.toml", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_leading_dashes_pipes_and_backticks() {
+        let content = "root/\n  -v\n  |pipe\n  `tick`\n  --|-- odd\n";
+        assert_eq!(
+            parse(content),
+            vec![
+                entry(0, "root", true),
+                entry(1, "-v", false),
+                entry(1, "|pipe", false),
+                entry(1, "`tick`", false),
+                entry(1, "--|-- odd", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_trailing_markers_without_tree_f() {
+        let content = ".\n├── run*\n├── a=b=\n├── mail@\n└── x|\n";
+        assert_eq!(
+            parse(content),
+            vec![
+                entry(0, ".", false),
+                entry(1, "run*", false),
+                entry(1, "a=b=", false),
+                entry(1, "mail@", false),
+                entry(1, "x|", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn strips_a_single_tree_f_classifier() {
+        let content = "./\n├── bin/\n│   └── run**\n├── link@ -> bin/run\n└── socket=\n";
+        let nodes = parse_tree_dsl(content).unwrap();
+        let children = &nodes[0].children;
+        assert_eq!(children[0].name, "bin");
+        assert!(matches!(children[0].entry.entry_type, FsEntryType::Folder));
+        assert_eq!(children[0].children[0].name, "run*");
+        assert_eq!(children[1].name, "link");
+        assert_eq!(children[1].entry.link_target.as_deref(), Some("bin/run"));
+        assert_eq!(children[2].name, "socket");
+    }
+}
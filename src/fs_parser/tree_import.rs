@@ -0,0 +1,287 @@
+use super::fs_structs::{FlatFsEntry, FsEntryType};
+use super::read_source;
+use super::tree_dsl::parse_tree_dsl;
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, TimeZone};
+use log::debug;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Node of an imported tree, before it is flattened
+pub struct ImportedNode {
+    pub name: String,
+    /// Entry metadata. `name`, `path` and `depth` are filled in when flattening.
+    pub entry: FlatFsEntry,
+    pub children: Vec<ImportedNode>,
+}
+
+/// A structure read from a tree description instead of the filesystem
+pub struct ImportedTree {
+    /// Name of the root, if the tree has a single top-level folder (like `tree` output does)
+    pub root_name: Option<String>,
+    /// Entries relative to the root, in the order they were given
+    pub entries: Vec<FlatFsEntry>,
+}
+
+/// Reads a tree description from a file, or from stdin if `source` is `-`.
+///
+/// The format is detected from the content: `tree -J` JSON output, `tree -X` XML output, or
+/// a hand-authored tree (see [`parse_tree_dsl`]).
+pub fn read_tree(source: &Path) -> Result<ImportedTree, String> {
+    let content = read_source(source)?;
+    let trimmed = content.trim_start();
+
+    let nodes = if trimmed.starts_with('[') {
+        parse_tree_json(&content)?
+    } else if trimmed.starts_with('<') {
+        parse_tree_xml(&content)?
+    } else {
+        parse_tree_dsl(&content)?
+    };
+
+    Ok(into_imported_tree(nodes))
+}
+
+/// Flattens the nodes. A single top-level folder becomes the root, like the `.` of `tree`.
+fn into_imported_tree(mut nodes: Vec<ImportedNode>) -> ImportedTree {
+    let mut entries = Vec::new();
+
+    let has_single_root = nodes.len() == 1
+        && (matches!(nodes[0].entry.entry_type, FsEntryType::Folder)
+            || !nodes[0].children.is_empty());
+    if has_single_root {
+        let root = nodes.remove(0);
+        entries.push(FlatFsEntry {
+            entry_type: FsEntryType::Folder,
+            path: PathBuf::new(),
+            ..root.entry
+        });
+        flatten_nodes(root.children, Path::new(""), &mut entries);
+        return ImportedTree {
+            root_name: Some(root.name),
+            entries,
+        };
+    }
+
+    flatten_nodes(nodes, Path::new(""), &mut entries);
+    ImportedTree {
+        root_name: None,
+        entries,
+    }
+}
+
+fn flatten_nodes(nodes: Vec<ImportedNode>, parent_path: &Path, entries: &mut Vec<FlatFsEntry>) {
+    for node in nodes {
+        let path = parent_path.join(&node.name);
+        let entry_type = if node.children.is_empty() {
+            node.entry.entry_type
+        } else {
+            FsEntryType::Folder
+        };
+        entries.push(FlatFsEntry {
+            entry_type,
+            path: path.clone(),
+            ..node.entry
+        });
+        flatten_nodes(node.children, &path, entries);
+    }
+}
+
+/// Parses the output of `tree -J`
+fn parse_tree_json(content: &str) -> Result<Vec<ImportedNode>, String> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    let items = value
+        .as_array()
+        .ok_or("Expected a JSON array, as printed by `tree -J`")?;
+    Ok(items.iter().filter_map(json_node).collect())
+}
+
+fn json_node(value: &Value) -> Option<ImportedNode> {
+    // Skips `report` and `error` nodes
+    let entry_type = match value.get("type")?.as_str()? {
+        "directory" => FsEntryType::Folder,
+        "file" | "link" => FsEntryType::File,
+        _ => return None,
+    };
+    let children = value
+        .get("contents")
+        .and_then(Value::as_array)
+        .map(|contents| contents.iter().filter_map(json_node).collect())
+        .unwrap_or_default();
+
+    Some(ImportedNode {
+        name: value.get("name")?.as_str()?.to_string(),
+        entry: FlatFsEntry {
+            entry_type,
+            size: value.get("size").and_then(Value::as_u64),
+            modified: value.get("time").and_then(|time| match time {
+                Value::Number(n) => n.as_i64(),
+                Value::String(s) => parse_tree_time(s),
+                _ => None,
+            }),
+            link_target: value
+                .get("target")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            ..Default::default()
+        },
+        children,
+    })
+}
+
+/// Parses the output of `tree -X`
+fn parse_tree_xml(content: &str) -> Result<Vec<ImportedNode>, String> {
+    let document =
+        roxmltree::Document::parse(content).map_err(|e| format!("Failed to parse XML: {}", e))?;
+    Ok(document
+        .root_element()
+        .children()
+        .filter_map(xml_node)
+        .collect())
+}
+
+fn xml_node(node: roxmltree::Node) -> Option<ImportedNode> {
+    // Skips `report` and `error` nodes, as well as text
+    let entry_type = match node.tag_name().name() {
+        "directory" => FsEntryType::Folder,
+        "file" | "link" => FsEntryType::File,
+        _ => return None,
+    };
+
+    Some(ImportedNode {
+        name: node.attribute("name")?.to_string(),
+        entry: FlatFsEntry {
+            entry_type,
+            size: node.attribute("size").and_then(|s| s.parse().ok()),
+            modified: node.attribute("time").and_then(parse_tree_time),
+            link_target: node.attribute("target").map(str::to_string),
+            ..Default::default()
+        },
+        children: node.children().filter_map(xml_node).collect(),
+    })
+}
+
+/// Parses a time printed by `tree -D` into a unix timestamp. Supports unix timestamps
+/// (`--timefmt %s`), ISO dates (`--timefmt '%F %T'`) and the default `tree` formats.
+fn parse_tree_time(time: &str) -> Option<i64> {
+    let time = time.trim();
+    if let Ok(timestamp) = time.parse::<i64>() {
+        return Some(timestamp);
+    }
+
+    let local_timestamp = |dt: NaiveDateTime| {
+        Local
+            .from_local_datetime(&dt)
+            .earliest()
+            .map(|t| t.timestamp())
+    };
+
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(time, format) {
+            return local_timestamp(dt);
+        }
+    }
+    for format in ["%Y-%m-%d", "%b %e %Y"] {
+        if let Ok(date) = NaiveDate::parse_from_str(time, format) {
+            return local_timestamp(date.and_hms_opt(0, 0, 0)?);
+        }
+    }
+
+    // Recent files are printed without a year, so they are within the last year
+    let now = Local::now();
+    let with_year = format!("{} {}", now.year(), time);
+    if let Ok(dt) = NaiveDateTime::parse_from_str(&with_year, "%Y %b %e %H:%M") {
+        let timestamp = local_timestamp(dt)?;
+        if timestamp <= now.timestamp() {
+            return Some(timestamp);
+        }
+        return local_timestamp(dt.with_year(now.year() - 1)?);
+    }
+
+    debug!("Could not parse time '{}'", time);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_tree_json() {
+        let content = r#"[
+            {"type": "directory", "name": ".", "contents": [
+                {"type": "directory", "name": "src", "time": 1700000000, "contents": [
+                    {"type": "file", "name": "main.rs", "size": 120}
+                ]},
+                {"type": "link", "name": "latest", "target": "src/main.rs"}
+            ]},
+            {"type": "report", "directories": 1, "files": 2}
+        ]"#;
+        let tree = into_imported_tree(parse_tree_json(content).unwrap());
+        assert_eq!(tree.root_name.as_deref(), Some("."));
+        let paths: Vec<&Path> = tree.entries.iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new(""),
+                Path::new("src"),
+                Path::new("src/main.rs"),
+                Path::new("latest"),
+            ]
+        );
+        assert_eq!(tree.entries[1].entry_type, FsEntryType::Folder);
+        assert_eq!(tree.entries[1].modified, Some(1700000000));
+        assert_eq!(tree.entries[2].size, Some(120));
+        assert_eq!(tree.entries[3].link_target.as_deref(), Some("src/main.rs"));
+    }
+
+    #[test]
+    fn imports_tree_xml() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <tree>
+              <directory name="project">
+                <directory name="docs" time="2024-01-31 12:00:00">
+                  <file name="guide.md" size="2048"></file>
+                </directory>
+                <file name="README.md"></file>
+              </directory>
+              <report><directories>1</directories><files>2</files></report>
+            </tree>"#;
+        let tree = into_imported_tree(parse_tree_xml(content).unwrap());
+        assert_eq!(tree.root_name.as_deref(), Some("project"));
+        let names: Vec<&Path> = tree.entries.iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(
+            names,
+            vec![
+                Path::new(""),
+                Path::new("docs"),
+                Path::new("docs/guide.md"),
+                Path::new("README.md"),
+            ]
+        );
+        assert!(tree.entries[1].modified.is_some());
+        assert_eq!(tree.entries[2].size, Some(2048));
+        assert_eq!(tree.entries[3].entry_type, FsEntryType::File);
+    }
+
+    #[test]
+    fn keeps_several_top_level_entries_without_root() {
+        let content = r#"[{"type": "file", "name": "a"}, {"type": "file", "name": "b"}]"#;
+        let tree = into_imported_tree(parse_tree_json(content).unwrap());
+        assert_eq!(tree.root_name, None);
+        assert_eq!(tree.entries.len(), 2);
+    }
+
+    #[test]
+    fn rejects_json_that_is_not_a_list() {
+        assert!(parse_tree_json(r#"{"type": "file"}"#).is_err());
+    }
+
+    #[test]
+    fn parses_tree_times() {
+        assert_eq!(parse_tree_time("1700000000"), Some(1700000000));
+        assert!(parse_tree_time("2024-01-31 12:00:00").is_some());
+        assert!(parse_tree_time("Jan 31 2024").is_some());
+        assert_eq!(parse_tree_time("yesterday"), None);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType, LineCounts};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files with a NUL byte in their first bytes are treated as binary and not counted
+const BINARY_CHECK_LEN: usize = 8000;
+
+/// How comments are written in a language
+struct CommentSyntax {
+    line: &'static [&'static str],
+    block: Option<(&'static str, &'static str)>,
+}
+
+const C_LIKE: CommentSyntax = CommentSyntax {
+    line: &["//"],
+    block: Some(("/*", "*/")),
+};
+const HASH: CommentSyntax = CommentSyntax {
+    line: &["#"],
+    block: None,
+};
+const DOUBLE_DASH: CommentSyntax = CommentSyntax {
+    line: &["--"],
+    block: None,
+};
+const MARKUP: CommentSyntax = CommentSyntax {
+    line: &[],
+    block: Some(("<!--", "-->")),
+};
+const CSS: CommentSyntax = CommentSyntax {
+    line: &[],
+    block: Some(("/*", "*/")),
+};
+const LISP: CommentSyntax = CommentSyntax {
+    line: &[";"],
+    block: None,
+};
+const TEX: CommentSyntax = CommentSyntax {
+    line: &["%"],
+    block: None,
+};
+/// Languages without comments only have code and blank lines
+const NONE: CommentSyntax = CommentSyntax {
+    line: &[],
+    block: None,
+};
+
+/// Picks the comment syntax of a file by its extension, or its name for files without one
+fn comment_syntax(path: &Path) -> &'static CommentSyntax {
+    let extension = path
+        .extension()
+        .or_else(|| path.file_name())
+        .map(|e| e.to_string_lossy().trim_start_matches('.').to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "rs" | "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "cs" | "java" | "kt" | "kts"
+        | "scala" | "go" | "swift" | "dart" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx"
+        | "php" | "zig" | "proto" | "groovy" | "gradle" | "scss" | "less" | "jsonc" => &C_LIKE,
+        "py" | "sh" | "bash" | "zsh" | "fish" | "rb" | "pl" | "r" | "yaml" | "yml" | "toml"
+        | "ini" | "cfg" | "conf" | "cmake" | "nix" | "ps1" | "ex" | "exs" | "jl" | "tf"
+        | "dockerfile" | "makefile" | "gitignore" | "editorconfig" => &HASH,
+        "sql" | "lua" | "hs" | "elm" | "ada" => &DOUBLE_DASH,
+        "html" | "htm" | "xml" | "svg" | "xhtml" | "vue" | "md" | "markdown" => &MARKUP,
+        "css" => &CSS,
+        "lisp" | "el" | "clj" | "cljs" | "scm" | "asm" | "s" => &LISP,
+        "tex" | "sty" | "erl" => &TEX,
+        _ => &NONE,
+    }
+}
+
+/// Counts the code, comment and blank lines of every text file in `entries`, which are
+/// relative to `root`. Binary and unreadable files are skipped.
+pub fn count_lines(root: &Path, entries: &[FlatFsEntry]) -> HashMap<PathBuf, LineCounts> {
+    entries
+        .iter()
+        .filter(|entry| matches!(entry.entry_type, FsEntryType::File))
+        .filter_map(|entry| {
+            let content = fs::read(root.join(&entry.path)).ok()?;
+            if content.iter().take(BINARY_CHECK_LEN).any(|byte| *byte == 0) {
+                return None;
+            }
+            let content = String::from_utf8_lossy(&content);
+            Some((
+                entry.path.clone(),
+                count_content(&content, comment_syntax(&entry.path)),
+            ))
+        })
+        .collect()
+}
+
+/// Classifies each line of a file. Lines with code and a comment count as code, lines that
+/// are only comment, or inside a block comment, count as comment.
+fn count_content(content: &str, syntax: &CommentSyntax) -> LineCounts {
+    let mut counts = LineCounts::default();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if in_block {
+            counts.comment += 1;
+            if let Some((_, end)) = syntax.block
+                && line.contains(end)
+            {
+                in_block = false;
+            }
+        } else if line.is_empty() {
+            counts.blank += 1;
+        } else if syntax.line.iter().any(|start| line.starts_with(start)) {
+            counts.comment += 1;
+        } else if let Some((start, end)) = syntax.block
+            && let Some(rest) = line.strip_prefix(start)
+        {
+            counts.comment += 1;
+            in_block = !rest.contains(end);
+        } else {
+            counts.code += 1;
+            // A block comment opened after code continues on the next lines
+            if let Some((start, end)) = syntax.block
+                && let Some(index) = line.rfind(start)
+            {
+                in_block = !line[index + start.len()..].contains(end);
+            }
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(code: u64, comment: u64, blank: u64) -> LineCounts {
+        LineCounts {
+            code,
+            comment,
+            blank,
+        }
+    }
+
+    #[test]
+    fn line_comments_and_blank_lines() {
+        let content = "# setup\n\nimport os  # trailing\n    # indented\nprint(os.name)\n";
+        assert_eq!(count_content(content, &HASH), counts(2, 2, 1));
+    }
+
+    #[test]
+    fn block_comments_span_lines() {
+        let content = "/* one line */\n/*\n * inside\n\n */\nfn main() {} /* opened\nafter code */\nlet a = 1;\n";
+        assert_eq!(count_content(content, &C_LIKE), counts(2, 6, 0));
+    }
+
+    #[test]
+    fn closed_block_after_code_does_not_continue() {
+        let content = "int a; /* note */\nint b;\n";
+        assert_eq!(count_content(content, &C_LIKE), counts(2, 0, 0));
+    }
+
+    #[test]
+    fn languages_without_comments_only_have_code() {
+        let content = "// not a comment\n\n{}\n";
+        assert_eq!(count_content(content, &NONE), counts(2, 0, 1));
+    }
+
+    #[test]
+    fn syntax_by_extension_or_name() {
+        assert_eq!(comment_syntax(Path::new("src/main.rs")).line, ["//"]);
+        assert_eq!(comment_syntax(Path::new("Dockerfile")).line, ["#"]);
+        assert_eq!(comment_syntax(Path::new(".gitignore")).line, ["#"]);
+        assert!(comment_syntax(Path::new("data.bin")).line.is_empty());
+    }
+}
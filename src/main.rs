@@ -1,14 +1,42 @@
+mod annotations;
+use annotations::load_annotations;
 mod args;
-use args::Args;
+use args::area_by::AreaBy;
+use args::color_by::ColorBy;
+use args::filetypes::Filetype;
+use args::layout::Layout;
+use args::size_mode::SizeMode;
+use args::{Args, Command};
+mod coverage;
+use coverage::load_coverage;
+mod diagnostics;
+use diagnostics::load_diagnostics;
+mod diff;
 use clap::Parser;
+use diff::{diff_trees, hash_files};
 use log::debug;
 mod fs_parser;
-use fs_parser::parse_fs_flat;
-use std::path::PathBuf;
+use fs_parser::archive::{is_archive, read_archive};
+use fs_parser::git_history::{read_churn, read_last_changed};
+use fs_parser::git_rev::read_git_tree;
+use fs_parser::git_status::read_git_status;
+use fs_parser::path_list::read_path_list;
+use fs_parser::synthetic::{SyntheticFilters, flatten_synthetic};
+use fs_parser::tree_import::read_tree;
+use fs_parser::{normalize_focus_paths, parse_fs_flat};
+mod loc;
+use loc::count_lines;
+mod owners;
+use owners::load_codeowners;
+mod sizes;
+use sizes::{compressed_sizes, entry_sizes, read_sizes, synthetic_sizes};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process;
 mod visualize;
-use visualize::visualize;
+use visualize::{RenderOptions, visualize};
 mod transform;
-use transform::do_transforms;
+use transform::{Overlays, do_transforms};
 
 fn main() {
     env_logger::init();
@@ -16,18 +44,288 @@ fn main() {
     let args = Args::parse();
     debug!("Parsed Args: {:#?}", args);
 
-    let mut filestructure = parse_fs_flat(
-        args.input_path,
-        args.depth,
-        args.include_root,
-        args.excludes,
-        args.use_gitignore,
-        args.ignore_hidden,
-    );
+    // A path list is read instead of walking the filesystem for `--from-paths` or `glimmr -`
+    let path_list_source = match (&args.from_paths, &args.input_path) {
+        (Some(source), _) => Some(source.clone()),
+        (None, Some(input)) if input == Path::new("-") => Some(input.clone()),
+        _ => None,
+    };
+    let root_name = match &args.input_path {
+        Some(input) if input != Path::new("-") => root_name(input),
+        _ => ".".to_string(),
+    };
+    let status_path = match &args.input_path {
+        Some(input) if input != Path::new("-") => input.clone(),
+        _ => PathBuf::from("."),
+    };
+    // Overlays counting every file below the root walk it again with the same filters
+    let excludes = args.excludes.clone();
+    // Pages split the rows of the list, other layouts only make sense as a whole
+    if args.page_rows.is_some()
+        && args.layout != Layout::List
+        && matches!(args.filetype, Filetype::SVG | Filetype::PNG)
+    {
+        exit_with_error(
+            "Could not paginate",
+            format!("the {} layout can't be split into pages", args.layout),
+        );
+    }
+    // Likewise only the rows of the list wrap into columns
+    if (args.columns.is_some() || args.max_rows.is_some())
+        && args.layout != Layout::List
+        && matches!(args.filetype, Filetype::SVG | Filetype::PNG)
+    {
+        exit_with_error(
+            "Could not wrap into columns",
+            format!("the {} layout can't be wrapped into columns", args.layout),
+        );
+    }
+    // Miller columns expand the focus paths
+    if args.layout == Layout::MillerColumns && args.focus.is_empty() {
+        exit_with_error(
+            "Could not lay out miller columns",
+            "at least one --focus path is required".to_string(),
+        );
+    }
+    let walks_filesystem = args.command.is_none()
+        && path_list_source.is_none()
+        && args.from_tree.is_none()
+        && args.git_rev.is_none()
+        && status_path.is_dir();
+    // The sides of a diff replace the other inputs
+    if args.command.is_some()
+        && (args.input_path.is_some()
+            || args.from_paths.is_some()
+            || args.from_tree.is_some()
+            || args.git_rev.is_some())
+    {
+        exit_with_error(
+            "Could not diff",
+            "input_path, --from-paths, --from-tree and --git-rev don't apply to diff, pass the \
+             sides instead"
+                .to_string(),
+        );
+    }
+    // Path lists, trees, revisions and archives have no .gitignore files to respect
+    let archive = args.input_path.as_deref().filter(|p| is_archive(p));
+    if args.use_gitignore
+        && (path_list_source.is_some()
+            || args.from_tree.is_some()
+            || args.git_rev.is_some()
+            || archive.is_some())
+    {
+        exit_with_error(
+            "Could not use .gitignore files",
+            "--use-gitignore only applies to directories".to_string(),
+        );
+    }
+    let filters = SyntheticFilters {
+        max_depth: args.depth,
+        include_root: args.include_root,
+        excludes: args.excludes.clone(),
+        ignore_hidden: args.ignore_hidden,
+        focus: normalize_focus_paths(&status_path, args.focus.clone()),
+    };
+
+    // Scaled layouts measure entries without `--loc` or `--sizes` as well
+    let scales_by = |area_by| {
+        matches!(args.layout, Layout::Treemap | Layout::Sunburst) && args.area_by == area_by
+    };
+    let count_loc = args.loc || scales_by(AreaBy::Loc);
+    let size_mode = args
+        .sizes
+        .or(scales_by(AreaBy::Size).then_some(SizeMode::Apparent));
+
+    // Path lists, trees, revisions and archives are read as a whole with their root name and
+    // whether their entries are sorted by name
+    let synthetic = if let Some(source) = path_list_source {
+        let entries = read_path_list(&source)
+            .unwrap_or_else(|e| exit_with_error("Could not read path list", e));
+        Some((root_name, entries, true))
+    } else if let Some(source) = &args.from_tree {
+        let tree = read_tree(source).unwrap_or_else(|e| exit_with_error("Could not read tree", e));
+        // Imported trees keep the order they were given in
+        Some((tree.root_name.unwrap_or(root_name), tree.entries, false))
+    } else if let Some(rev) = &args.git_rev {
+        let repo_path = args.input_path.as_deref().unwrap_or(Path::new("."));
+        // Focus paths are expanded up to the depth below them. Folder sizes need the whole
+        // tree.
+        let max_depth = if size_mode.is_some() {
+            u32::MAX
+        } else {
+            filters
+                .focus
+                .iter()
+                .map(|f| f.components().count() as u32)
+                .max()
+                .unwrap_or(0)
+                .saturating_add(args.depth)
+        };
+        let entries = read_git_tree(repo_path, rev, max_depth)
+            .unwrap_or_else(|e| exit_with_error("Could not read git revision", e));
+        Some((root_name, entries, true))
+    } else if let Some(archive) = archive {
+        let entries =
+            read_archive(archive).unwrap_or_else(|e| exit_with_error("Could not read archive", e));
+        Some((root_name, entries, true))
+    } else {
+        None
+    };
+
+    let mut filestructure = if let Some(Command::Diff { old, new }) = &args.command {
+        diff_trees(old, new, &filters, args.use_gitignore)
+            .unwrap_or_else(|e| exit_with_error("Could not diff", e))
+    } else if let Some((name, entries, sort_by_name)) = &synthetic {
+        flatten_synthetic(name, entries.clone(), &filters, *sort_by_name)
+    } else {
+        let input_path = args
+            .input_path
+            .expect("input_path is required without another input");
+        let mut filestructure = parse_fs_flat(
+            input_path.clone(),
+            args.depth,
+            args.include_root,
+            args.excludes,
+            args.use_gitignore,
+            args.ignore_hidden,
+            args.focus,
+        );
+        // Snapshots carry content hashes so they can be diffed later
+        if matches!(args.filetype, Filetype::JSON) {
+            hash_files(&input_path, &mut filestructure);
+        }
+        filestructure
+    };
 
     debug!("Parsed filestructure: {:#?}", filestructure);
 
-    filestructure = do_transforms(filestructure, args.collapse_folders);
+    let selected_paths: &[PathBuf] = if args.layout == Layout::MillerColumns {
+        if let Some(missing) = filters
+            .focus
+            .iter()
+            .find(|focus| !filestructure.iter().any(|entry| entry.path == **focus))
+        {
+            exit_with_error(
+                "Could not lay out miller columns",
+                format!("focus path '{}' was not found", missing.display()),
+            );
+        }
+        &filters.focus
+    } else {
+        &[]
+    };
+
+    let mut overlays = Overlays {
+        annotations: load_annotations(args.annotations, args.annotate)
+            .unwrap_or_else(|e| exit_with_error("Could not load annotations", e)),
+        ..Default::default()
+    };
+    if args.git_status {
+        // The working tree status only describes the entries of a directory
+        if !walks_filesystem {
+            exit_with_error(
+                "Could not read git status",
+                "--git-status only applies to directories".to_string(),
+            );
+        }
+        overlays.git_statuses = read_git_status(&status_path)
+            .unwrap_or_else(|e| exit_with_error("Could not read git status", e));
+    }
+    if args.owners {
+        overlays.codeowners = Some(
+            load_codeowners(&status_path)
+                .unwrap_or_else(|e| exit_with_error("Could not load CODEOWNERS", e)),
+        );
+    }
+    if let Some(report) = &args.coverage {
+        overlays.coverage = load_coverage(report, &status_path)
+            .unwrap_or_else(|e| exit_with_error("Could not load coverage", e));
+    }
+    if let Some(report) = &args.diagnostics {
+        overlays.diagnostics = load_diagnostics(report, &status_path)
+            .unwrap_or_else(|e| exit_with_error("Could not load diagnostics", e));
+    }
+    // Ages always look at the whole history up to the drawn revision
+    if args.color_by == Some(ColorBy::Age) && (args.since.is_some() || args.rev_range.is_some()) {
+        exit_with_error(
+            "Could not color by age",
+            "--since and --rev-range only apply to --color-by commits and lines".to_string(),
+        );
+    }
+    // Lines are read from the files on disk, which only match the entries of a directory
+    if count_loc && !walks_filesystem {
+        exit_with_error(
+            "Could not count lines",
+            "--loc and --area-by loc only apply to directories".to_string(),
+        );
+    }
+    let all_files = if walks_filesystem && (count_loc || size_mode.is_some()) {
+        parse_fs_flat(
+            status_path.clone(),
+            u32::MAX - 1,
+            false,
+            excludes,
+            args.use_gitignore,
+            args.ignore_hidden,
+            Vec::new(),
+        )
+    } else {
+        Vec::new()
+    };
+    if count_loc {
+        overlays.loc = count_lines(&status_path, &all_files);
+    }
+    if let Some(mode) = size_mode {
+        // Archives, git revisions and imported trees carry the sizes of their files, zip
+        // archives their compressed sizes as well
+        if walks_filesystem {
+            overlays.sizes = read_sizes(&status_path, &all_files, mode);
+        } else if mode == SizeMode::Disk {
+            exit_with_error(
+                "Could not measure sizes on disk",
+                "only directories have sizes on disk, use --sizes for apparent sizes".to_string(),
+            );
+        } else if let Some((_, entries, _)) = synthetic {
+            (overlays.sizes, overlays.compressed_sizes) = synthetic_sizes(entries, &filters);
+        } else {
+            overlays.sizes = entry_sizes(&filestructure);
+            overlays.compressed_sizes = compressed_sizes(&filestructure);
+        }
+    }
+    match args.color_by {
+        Some(color_by @ (ColorBy::Commits | ColorBy::Lines)) => {
+            overlays.churn = read_churn(
+                &status_path,
+                color_by == ColorBy::Lines,
+                args.since,
+                args.rev_range.as_deref(),
+            )
+            .unwrap_or_else(|e| exit_with_error("Could not read git history", e));
+        }
+        // Outside of git, the modification times of the entries are used instead. Imported
+        // trees and archives carry their own times, unrelated to the history around them.
+        Some(ColorBy::Age) if walks_filesystem || args.git_rev.is_some() => {
+            overlays.last_changed = read_last_changed(&status_path, args.git_rev.as_deref())
+                .unwrap_or_else(|e| {
+                    eprintln!(
+                        "Could not read git history, using modification times: {}",
+                        e
+                    );
+                    HashMap::new()
+                });
+        }
+        Some(ColorBy::Age) | None => {}
+    }
+
+    filestructure = do_transforms(
+        filestructure,
+        args.collapse_folders,
+        args.sort_by_size,
+        &args.highlight,
+        args.dim_unhighlighted,
+        selected_paths,
+        &overlays,
+    );
 
     debug!("Transformed filestructure: {:#?}", filestructure);
 
@@ -36,6 +334,27 @@ fn main() {
         args.theme.get_theme(),
         args.filetype,
         PathBuf::from(args.output_filepath),
-        args.bake_font,
+        RenderOptions {
+            bake_font: args.bake_font,
+            color_by: args.color_by,
+            show_sizes: args.sizes.is_some(),
+            layout: args.layout,
+            area_by: args.area_by,
+            columns: args.columns,
+            max_rows: args.max_rows,
+            page_rows: args.page_rows,
+        },
     );
 }
+
+/// Name shown for the root entry of a path, e.g. `src` for `./src`
+fn root_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn exit_with_error(context: &str, error: String) -> ! {
+    eprintln!("{}: {}", context, error);
+    process::exit(1);
+}
@@ -0,0 +1,166 @@
+use git2::Repository;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where CODEOWNERS files are looked for, relative to the repository root. The first one found
+/// is used.
+const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// The rules of a CODEOWNERS file
+#[derive(Debug)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+    /// Path of the visualized root relative to the repository root
+    prefix: PathBuf,
+}
+
+#[derive(Debug)]
+struct Rule {
+    matcher: GlobSet,
+    owners: Vec<String>,
+}
+
+impl CodeOwners {
+    /// Returns the owners of `path`, relative to the visualized root. The last matching rule
+    /// wins, so a rule without owners unsets the owners of earlier rules.
+    pub fn owners_of(&self, path: &Path) -> Vec<String> {
+        let path = self.prefix.join(path);
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matcher.is_match(&path))
+            .map(|rule| rule.owners.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Loads the CODEOWNERS file of the repository containing `path`. Outside of git, `path` itself
+/// is treated as the repository root.
+pub fn load_codeowners(path: &Path) -> Result<CodeOwners, String> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{}': {}", path.display(), e))?;
+    let root = Repository::discover(&path)
+        .ok()
+        .and_then(|repo| repo.workdir().and_then(|w| w.canonicalize().ok()))
+        .unwrap_or_else(|| path.clone());
+    let prefix = path
+        .strip_prefix(&root)
+        .unwrap_or(Path::new(""))
+        .to_path_buf();
+
+    let file = CODEOWNERS_LOCATIONS
+        .iter()
+        .map(|location| root.join(location))
+        .find(|file| file.is_file())
+        .ok_or_else(|| {
+            format!(
+                "No CODEOWNERS file found in '{}'. Expected one of {}.",
+                root.display(),
+                CODEOWNERS_LOCATIONS.join(", ")
+            )
+        })?;
+    let content = fs::read_to_string(&file)
+        .map_err(|e| format!("Failed to read '{}': {}", file.display(), e))?;
+
+    let rules = parse_codeowners(&content)?;
+    Ok(CodeOwners { rules, prefix })
+}
+
+/// Parses the lines of a CODEOWNERS file into rules. Each line is a pattern followed by its
+/// owners. Comments and section headers are skipped.
+fn parse_codeowners(content: &str) -> Result<Vec<Rule>, String> {
+    let mut rules = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        let Some(pattern) = tokens.next() else {
+            continue;
+        };
+        let owners = tokens
+            .take_while(|token| !token.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+
+        let mut builder = GlobSetBuilder::new();
+        for glob in pattern_globs(pattern) {
+            builder.add(
+                GlobBuilder::new(&glob)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|e| format!("Invalid pattern on line {}: {}", i + 1, e))?,
+            );
+        }
+        let matcher = builder
+            .build()
+            .map_err(|e| format!("Invalid pattern on line {}: {}", i + 1, e))?;
+        rules.push(Rule { matcher, owners });
+    }
+    Ok(rules)
+}
+
+/// Translates a CODEOWNERS pattern into globs. Like in `.gitignore`, patterns with a slash are
+/// relative to the root, others match at any depth. A pattern naming a folder, either with a
+/// trailing slash or as a plain path without wildcards, also matches everything inside it.
+fn pattern_globs(pattern: &str) -> Vec<String> {
+    let trimmed = pattern.trim_start_matches('/').trim_end_matches('/');
+    let anchored = pattern.starts_with('/') || trimmed.contains('/');
+    let base = if anchored {
+        trimmed.to_string()
+    } else {
+        format!("**/{}", trimmed)
+    };
+    let last_component = trimmed.rsplit('/').next().unwrap_or_default();
+    let names_folder = pattern.ends_with('/') || !last_component.contains(['*', '?', '[']);
+    if names_folder {
+        vec![format!("{}/**", base), base]
+    } else {
+        vec![base]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owners(content: &str, path: &str) -> Vec<String> {
+        let owners = CodeOwners {
+            rules: parse_codeowners(content).unwrap(),
+            prefix: PathBuf::new(),
+        };
+        owners.owners_of(Path::new(path))
+    }
+
+    #[test]
+    fn wildcards_only_match_their_own_level() {
+        let content = "docs/* @docs\n";
+        assert_eq!(owners(content, "docs/a.md"), ["@docs"]);
+        assert!(owners(content, "docs/a/b.md").is_empty());
+    }
+
+    #[test]
+    fn folders_match_everything_inside() {
+        assert_eq!(owners("/docs/ @docs", "docs/a/b.md"), ["@docs"]);
+        assert_eq!(owners("docs @docs", "src/docs/a/b.md"), ["@docs"]);
+        assert_eq!(owners("/build/logs @ops", "build/logs/x/y.log"), ["@ops"]);
+        assert!(owners("/build/logs @ops", "src/build/logs/y.log").is_empty());
+    }
+
+    #[test]
+    fn unanchored_patterns_match_at_any_depth() {
+        assert_eq!(owners("*.rs @rust", "src/a/b.rs"), ["@rust"]);
+        assert!(owners("*.rs @rust", "src/a/b.rs.orig").is_empty());
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let content = "# owners\n* @all\n/src/ @src # inline comment\n/src/generated/\n";
+        assert_eq!(owners(content, "README.md"), ["@all"]);
+        assert_eq!(owners(content, "src/main.rs"), ["@src"]);
+        assert!(owners(content, "src/generated/a.rs").is_empty());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::args::size_mode::SizeMode;
+use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType};
+use crate::fs_parser::synthetic::{SyntheticFilters, flatten_synthetic};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+
+/// Reads the size of every file in `entries`, which are relative to `root`. Links are measured
+/// themselves, not their targets. Unreadable files are skipped.
+pub fn read_sizes(root: &Path, entries: &[FlatFsEntry], mode: SizeMode) -> HashMap<PathBuf, u64> {
+    entries
+        .iter()
+        .filter(|entry| matches!(entry.entry_type, FsEntryType::File))
+        .filter_map(|entry| {
+            let metadata = fs::symlink_metadata(root.join(&entry.path)).ok()?;
+            let size = match mode {
+                SizeMode::Apparent => metadata.len(),
+                SizeMode::Disk => disk_usage(&metadata),
+            };
+            Some((entry.path.clone(), size))
+        })
+        .collect()
+}
+
+/// Apparent sizes of the files in `entries` as read from an archive, git revision or imported
+/// tree
+pub fn entry_sizes(entries: &[FlatFsEntry]) -> HashMap<PathBuf, u64> {
+    entries
+        .iter()
+        .filter(|entry| matches!(entry.entry_type, FsEntryType::File))
+        .filter_map(|entry| Some((entry.path.clone(), entry.size?)))
+        .collect()
+}
+
+/// Compressed sizes of the files in `entries` as read from an archive. Only zip archives know
+/// them per entry.
+pub fn compressed_sizes(entries: &[FlatFsEntry]) -> HashMap<PathBuf, u64> {
+    entries
+        .iter()
+        .filter(|entry| matches!(entry.entry_type, FsEntryType::File))
+        .filter_map(|entry| Some((entry.path.clone(), entry.compressed_size?)))
+        .collect()
+}
+
+/// Apparent and compressed sizes of the files of an archive, git revision or imported tree.
+/// They are read from all of its `entries`, so folder totals include what the depth limit and
+/// focus paths leave out, like for directories.
+pub fn synthetic_sizes(
+    entries: Vec<FlatFsEntry>,
+    filters: &SyntheticFilters,
+) -> (HashMap<PathBuf, u64>, HashMap<PathBuf, u64>) {
+    let filters = SyntheticFilters {
+        max_depth: u32::MAX,
+        focus: Vec::new(),
+        ..filters.clone()
+    };
+    let entries = flatten_synthetic("", entries, &filters, false);
+    (entry_sizes(&entries), compressed_sizes(&entries))
+}
+
+#[cfg(unix)]
+fn disk_usage(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    // Blocks are always counted in units of 512 bytes, whatever the block size of the filesystem
+    metadata.blocks() * 512
+}
+
+#[cfg(not(unix))]
+fn disk_usage(metadata: &fs::Metadata) -> u64 {
+    metadata.len()
+}
+
+/// Formats a size in bytes with binary units, e.g. `340 B` or `1.2 MiB`
+pub fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::{Overlays, do_transforms};
+
+    fn file(path: &str, size: u64) -> FlatFsEntry {
+        FlatFsEntry {
+            path: PathBuf::from(path),
+            entry_type: FsEntryType::File,
+            size: Some(size),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn folders_at_the_depth_limit_sum_up_their_hidden_content() {
+        let entries = vec![
+            file("a/b/c.txt", 4000),
+            file("a/d.txt", 1000),
+            file("e.txt", 3),
+        ];
+        let filters = SyntheticFilters {
+            max_depth: 0,
+            ..Default::default()
+        };
+        let filestructure = flatten_synthetic("pkg", entries.clone(), &filters, true);
+        let (sizes, compressed) = synthetic_sizes(entries, &filters);
+        assert!(compressed.is_empty());
+
+        let overlays = Overlays {
+            sizes,
+            ..Default::default()
+        };
+        let filestructure = do_transforms(filestructure, false, false, &[], false, &[], &overlays);
+        let shown: Vec<(&str, Option<u64>)> = filestructure
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.size))
+            .collect();
+        assert_eq!(shown, [("a", Some(5000)), ("e.txt", Some(3))]);
+    }
+
+    #[test]
+    fn sizes_outside_the_focus_are_counted() {
+        let entries = vec![file("a/b.txt", 10), file("c/d.txt", 20)];
+        let filters = SyntheticFilters {
+            max_depth: 1,
+            focus: vec![PathBuf::from("c")],
+            ..Default::default()
+        };
+        let (sizes, _) = synthetic_sizes(entries, &filters);
+        assert_eq!(sizes.get(Path::new("a/b.txt")), Some(&10));
+    }
+
+    #[test]
+    fn sizes_use_binary_units() {
+        assert_eq!(format_size(340), "340 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
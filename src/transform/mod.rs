@@ -1,4 +1,33 @@
-use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType};
+use crate::fs_parser::fs_structs::{
+    Coverage, Diagnostics, FlatFsEntry, FsEntryType, GitStatus, Highlight, LineCounts,
+};
+use crate::owners::CodeOwners;
+use globset::{Glob, GlobSetBuilder};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Data per path that is attached to the entries. Empty maps are skipped.
+#[derive(Debug, Default)]
+pub struct Overlays {
+    pub annotations: HashMap<PathBuf, String>,
+    pub git_statuses: HashMap<PathBuf, GitStatus>,
+    /// Commits or changed lines per file
+    pub churn: HashMap<PathBuf, u64>,
+    /// Date of the last commit per file
+    pub last_changed: HashMap<PathBuf, i64>,
+    pub codeowners: Option<CodeOwners>,
+    /// Line coverage per file
+    pub coverage: HashMap<PathBuf, Coverage>,
+    /// Diagnostic counts per file
+    pub diagnostics: HashMap<PathBuf, Diagnostics>,
+    /// Line counts per text file
+    pub loc: HashMap<PathBuf, LineCounts>,
+    /// Size in bytes per file
+    pub sizes: HashMap<PathBuf, u64>,
+    /// Compressed size in bytes per file of an archive
+    pub compressed_sizes: HashMap<PathBuf, u64>,
+}
 
 fn collapse_folders(filestructure: Vec<FlatFsEntry>) -> Vec<FlatFsEntry> {
     let mut collapsed_structure = Vec::new();
@@ -23,6 +52,9 @@ fn collapse_folders(filestructure: Vec<FlatFsEntry>) -> Vec<FlatFsEntry> {
                 let child_index = child_indices[0];
                 if let FsEntryType::Folder = filestructure[child_index].entry_type {
                     let mut collapsed_name = current_entry.name.clone();
+                    let mut annotations: Vec<String> =
+                        current_entry.annotation.iter().cloned().collect();
+                    let mut selected = current_entry.selected;
                     let mut last_collapsed_index = i;
                     let mut current_parent_index = i;
 
@@ -43,6 +75,8 @@ fn collapse_folders(filestructure: Vec<FlatFsEntry>) -> Vec<FlatFsEntry> {
                             let single_child_index = children_of_current_parent[0];
                             if let FsEntryType::Folder = filestructure[single_child_index].entry_type {
                                 collapsed_name.push_str(&format!("/{}", filestructure[single_child_index].name));
+                                annotations.extend(filestructure[single_child_index].annotation.clone());
+                                selected |= filestructure[single_child_index].selected;
                                 last_collapsed_index = single_child_index;
                                 current_parent_index = single_child_index;
                             } else {
@@ -53,11 +87,14 @@ fn collapse_folders(filestructure: Vec<FlatFsEntry>) -> Vec<FlatFsEntry> {
                         }
                     }
 
-                    // Add the collapsed folder to the new structure
+                    // Add the collapsed folder to the new structure. It keeps the annotations
+                    // of every folder in the chain, and is selected if any of them is.
                     collapsed_structure.push(FlatFsEntry {
                         name: collapsed_name,
-                        entry_type: FsEntryType::Folder,
                         depth: current_entry.depth,
+                        annotation: (!annotations.is_empty()).then(|| annotations.join("; ")),
+                        selected,
+                        ..filestructure[last_collapsed_index].clone()
                     });
 
                     // Adjust the depth of the children of the last collapsed folder
@@ -85,12 +122,288 @@ fn collapse_folders(filestructure: Vec<FlatFsEntry>) -> Vec<FlatFsEntry> {
     collapsed_structure
 }
 
+/// Marks entries whose path matches one of the `patterns` globs, along with their ancestors.
+/// If `dim_others` is set, all remaining entries are marked to be dimmed.
+fn apply_highlights(
+    mut filestructure: Vec<FlatFsEntry>,
+    patterns: &[String],
+    dim_others: bool,
+) -> Vec<FlatFsEntry> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(e) => eprintln!("Could not add highlight pattern: {}", e),
+        }
+    }
+    let globset = match builder.build() {
+        Ok(globset) => globset,
+        Err(e) => {
+            eprintln!("Could not build highlight patterns: {}", e);
+            return filestructure;
+        }
+    };
+
+    // Walk backwards, so the ancestors of a match are reached after the match itself.
+    // `path_depth` is the depth an entry must have to be the next ancestor on a highlight path.
+    let mut path_depth: Option<u32> = None;
+    for entry in filestructure.iter_mut().rev() {
+        let is_ancestor = path_depth.is_some_and(|d| entry.depth == d);
+        entry.highlight = if globset.is_match(&entry.path) {
+            Highlight::Match
+        } else if is_ancestor {
+            Highlight::Path
+        } else if dim_others {
+            Highlight::Dimmed
+        } else {
+            Highlight::None
+        };
+
+        if entry.highlight == Highlight::Match || is_ancestor {
+            path_depth = entry.depth.checked_sub(1);
+        }
+    }
+
+    filestructure
+}
+
+/// Selects the entries at `paths` and the folders leading to them
+fn apply_selection(mut filestructure: Vec<FlatFsEntry>, paths: &[PathBuf]) -> Vec<FlatFsEntry> {
+    for entry in filestructure.iter_mut() {
+        entry.selected = paths.iter().any(|path| path.starts_with(&entry.path));
+    }
+    filestructure
+}
+
+/// Attaches the annotations to the entries with matching paths
+fn apply_annotations(
+    mut filestructure: Vec<FlatFsEntry>,
+    annotations: &HashMap<PathBuf, String>,
+) -> Vec<FlatFsEntry> {
+    for entry in filestructure.iter_mut() {
+        if let Some(annotation) = annotations.get(&entry.path) {
+            entry.annotation = Some(annotation.clone());
+        }
+    }
+    filestructure
+}
+
+/// Combines the values of files for each of their ancestors, so every folder gets the combined
+/// value of everything below it, including files that are hidden or no longer exist
+fn aggregate_to_ancestors<T: Copy>(
+    values: &HashMap<PathBuf, T>,
+    combine: impl Fn(T, T) -> T,
+) -> HashMap<&Path, T> {
+    let mut aggregated: HashMap<&Path, T> = HashMap::new();
+    for (path, value) in values {
+        for ancestor in path.ancestors() {
+            aggregated
+                .entry(ancestor)
+                .and_modify(|current| *current = combine(*current, *value))
+                .or_insert(*value);
+        }
+    }
+    aggregated
+}
+
+/// Tags the entries with their git status. Folders get the highest status of anything below
+/// them.
+fn apply_git_status(
+    mut filestructure: Vec<FlatFsEntry>,
+    statuses: &HashMap<PathBuf, GitStatus>,
+) -> Vec<FlatFsEntry> {
+    let aggregated = aggregate_to_ancestors(statuses, Ord::max);
+    for entry in filestructure.iter_mut() {
+        entry.git_status = aggregated.get(entry.path.as_path()).copied();
+    }
+    filestructure
+}
+
+/// Attaches the churn of each entry, summed up for folders
+fn apply_churn(
+    mut filestructure: Vec<FlatFsEntry>,
+    churn: &HashMap<PathBuf, u64>,
+) -> Vec<FlatFsEntry> {
+    let aggregated = aggregate_to_ancestors(churn, |a, b| a + b);
+    for entry in filestructure.iter_mut() {
+        entry.churn = aggregated.get(entry.path.as_path()).copied();
+    }
+    filestructure
+}
+
+/// Replaces the modification time of entries with the date of their last commit. Folders get
+/// the newest date of anything below them.
+fn apply_last_changed(
+    mut filestructure: Vec<FlatFsEntry>,
+    last_changed: &HashMap<PathBuf, i64>,
+) -> Vec<FlatFsEntry> {
+    let aggregated = aggregate_to_ancestors(last_changed, Ord::max);
+    for entry in filestructure.iter_mut() {
+        if let Some(time) = aggregated.get(entry.path.as_path()) {
+            entry.modified = Some(*time);
+        }
+    }
+    filestructure
+}
+
+/// Tags the entries with their owners. The root is skipped, it is not a path of its own.
+fn apply_owners(mut filestructure: Vec<FlatFsEntry>, codeowners: &CodeOwners) -> Vec<FlatFsEntry> {
+    for entry in filestructure.iter_mut() {
+        if !entry.path.as_os_str().is_empty() {
+            entry.owners = Some(codeowners.owners_of(&entry.path));
+        }
+    }
+    filestructure
+}
+
+/// Attaches the line coverage of each entry, summed up for folders
+fn apply_coverage(
+    mut filestructure: Vec<FlatFsEntry>,
+    coverage: &HashMap<PathBuf, Coverage>,
+) -> Vec<FlatFsEntry> {
+    let aggregated = aggregate_to_ancestors(coverage, |a, b| Coverage {
+        covered: a.covered + b.covered,
+        total: a.total + b.total,
+    });
+    for entry in filestructure.iter_mut() {
+        entry.coverage = aggregated.get(entry.path.as_path()).copied();
+    }
+    filestructure
+}
+
+/// Attaches the diagnostic counts of each entry, summed up for folders
+fn apply_diagnostics(
+    mut filestructure: Vec<FlatFsEntry>,
+    diagnostics: &HashMap<PathBuf, Diagnostics>,
+) -> Vec<FlatFsEntry> {
+    let aggregated = aggregate_to_ancestors(diagnostics, |a, b| Diagnostics {
+        errors: a.errors + b.errors,
+        warnings: a.warnings + b.warnings,
+        notes: a.notes + b.notes,
+    });
+    for entry in filestructure.iter_mut() {
+        entry.diagnostics = aggregated.get(entry.path.as_path()).copied();
+    }
+    filestructure
+}
+
+/// Attaches the line counts of each entry, summed up for folders
+fn apply_loc(
+    mut filestructure: Vec<FlatFsEntry>,
+    loc: &HashMap<PathBuf, LineCounts>,
+) -> Vec<FlatFsEntry> {
+    let aggregated = aggregate_to_ancestors(loc, |a, b| LineCounts {
+        code: a.code + b.code,
+        comment: a.comment + b.comment,
+        blank: a.blank + b.blank,
+    });
+    for entry in filestructure.iter_mut() {
+        entry.loc = aggregated.get(entry.path.as_path()).copied();
+    }
+    filestructure
+}
+
+/// Sets the size of each entry, summed up for folders
+fn apply_sizes(
+    mut filestructure: Vec<FlatFsEntry>,
+    sizes: &HashMap<PathBuf, u64>,
+) -> Vec<FlatFsEntry> {
+    let aggregated = aggregate_to_ancestors(sizes, |a, b| a + b);
+    for entry in filestructure.iter_mut() {
+        entry.size = aggregated.get(entry.path.as_path()).copied();
+    }
+    filestructure
+}
+
+/// Sets the compressed size of each entry of an archive, summed up for folders
+fn apply_compressed_sizes(
+    mut filestructure: Vec<FlatFsEntry>,
+    sizes: &HashMap<PathBuf, u64>,
+) -> Vec<FlatFsEntry> {
+    let aggregated = aggregate_to_ancestors(sizes, |a, b| a + b);
+    for entry in filestructure.iter_mut() {
+        entry.compressed_size = aggregated.get(entry.path.as_path()).copied();
+    }
+    filestructure
+}
+
+/// Orders the children of every folder by size, largest first. Entries of the same size keep
+/// their order.
+fn sort_by_size(filestructure: &[FlatFsEntry]) -> Vec<FlatFsEntry> {
+    let Some(depth) = filestructure.iter().map(|e| e.depth).min() else {
+        return Vec::new();
+    };
+    // Split into the subtrees of the top level entries, then sort each subtree the same way
+    let mut subtrees: Vec<&[FlatFsEntry]> = Vec::new();
+    let mut start = 0;
+    for i in 1..=filestructure.len() {
+        if i == filestructure.len() || filestructure[i].depth == depth {
+            subtrees.push(&filestructure[start..i]);
+            start = i;
+        }
+    }
+    subtrees.sort_by_key(|subtree| Reverse(subtree[0].size.unwrap_or(0)));
+
+    let mut sorted = Vec::with_capacity(filestructure.len());
+    for subtree in subtrees {
+        sorted.push(subtree[0].clone());
+        sorted.extend(sort_by_size(&subtree[1..]));
+    }
+    sorted
+}
+
 pub fn do_transforms(
-    filestructure: Vec<FlatFsEntry>,
+    mut filestructure: Vec<FlatFsEntry>,
     collapse_folders_flag: bool,
+    sort_by_size_flag: bool,
+    highlights: &[String],
+    dim_unhighlighted: bool,
+    selected_paths: &[PathBuf],
+    overlays: &Overlays,
 ) -> Vec<FlatFsEntry> {
+    if !overlays.annotations.is_empty() {
+        filestructure = apply_annotations(filestructure, &overlays.annotations);
+    }
+    if !overlays.git_statuses.is_empty() {
+        filestructure = apply_git_status(filestructure, &overlays.git_statuses);
+    }
+    if !overlays.churn.is_empty() {
+        filestructure = apply_churn(filestructure, &overlays.churn);
+    }
+    if !overlays.last_changed.is_empty() {
+        filestructure = apply_last_changed(filestructure, &overlays.last_changed);
+    }
+    if let Some(codeowners) = &overlays.codeowners {
+        filestructure = apply_owners(filestructure, codeowners);
+    }
+    if !overlays.coverage.is_empty() {
+        filestructure = apply_coverage(filestructure, &overlays.coverage);
+    }
+    if !overlays.diagnostics.is_empty() {
+        filestructure = apply_diagnostics(filestructure, &overlays.diagnostics);
+    }
+    if !overlays.loc.is_empty() {
+        filestructure = apply_loc(filestructure, &overlays.loc);
+    }
+    if !overlays.sizes.is_empty() {
+        filestructure = apply_sizes(filestructure, &overlays.sizes);
+    }
+    if !overlays.compressed_sizes.is_empty() {
+        filestructure = apply_compressed_sizes(filestructure, &overlays.compressed_sizes);
+    }
+    if sort_by_size_flag {
+        filestructure = sort_by_size(&filestructure);
+    }
+    if !selected_paths.is_empty() {
+        filestructure = apply_selection(filestructure, selected_paths);
+    }
     if collapse_folders_flag {
-        return collapse_folders(filestructure);
+        filestructure = collapse_folders(filestructure);
+    }
+    if !highlights.is_empty() {
+        filestructure = apply_highlights(filestructure, highlights, dim_unhighlighted);
     }
-    return filestructure;
+    filestructure
 }
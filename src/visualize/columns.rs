@@ -0,0 +1,52 @@
+use crate::fs_parser::fs_structs::{FlatFsEntry, LineCounts};
+use crate::sizes::format_size;
+
+/// A column of values shown right-aligned next to the labels, e.g. the line counts of `--loc`
+pub struct MetricColumn {
+    pub header: &'static str,
+    /// Shown after bare numbers in plain text, where there is no header
+    pub unit: Option<&'static str>,
+    /// Formatted value of each entry, `None` for entries without one
+    pub values: Vec<Option<String>>,
+}
+
+/// The metric columns of the entries, in the order they are shown. Metrics no entry has are
+/// left out. Sizes are only shown on request, as archives and git revisions always have them,
+/// followed by the compressed sizes of zip archives.
+pub fn metric_columns(filestructure: &[FlatFsEntry], show_sizes: bool) -> Vec<MetricColumn> {
+    let mut columns = Vec::new();
+    if show_sizes && filestructure.iter().any(|e| e.size.is_some()) {
+        columns.push(MetricColumn {
+            header: "size",
+            unit: None,
+            values: filestructure
+                .iter()
+                .map(|e| e.size.map(format_size))
+                .collect(),
+        });
+    }
+    if show_sizes && filestructure.iter().any(|e| e.compressed_size.is_some()) {
+        columns.push(MetricColumn {
+            header: "compressed",
+            unit: Some("compressed"),
+            values: filestructure
+                .iter()
+                .map(|e| e.compressed_size.map(format_size))
+                .collect(),
+        });
+    }
+    if filestructure.iter().any(|e| e.loc.is_some()) {
+        let loc_column = |header, count: fn(&LineCounts) -> u64| MetricColumn {
+            header,
+            unit: Some(header),
+            values: filestructure
+                .iter()
+                .map(|e| e.loc.as_ref().map(|loc| count(loc).to_string()))
+                .collect(),
+        };
+        columns.push(loc_column("code", |loc| loc.code));
+        columns.push(loc_column("comment", |loc| loc.comment));
+        columns.push(loc_column("blank", |loc| loc.blank));
+    }
+    columns
+}
@@ -1,9 +1,16 @@
-use crate::args::{filetypes::Filetype, themes::Theme};
+use crate::args::{
+    area_by::AreaBy, color_by::ColorBy, filetypes::Filetype, layout::Layout, themes::Theme,
+};
 use crate::fs_parser::fs_structs::FlatFsEntry;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+mod columns;
 mod svg_helper;
 use log::debug;
 use svg_helper::compose_svg_from_filestruct;
+mod txt_helper;
+mod wrap;
+use txt_helper::compose_txt_from_filestruct;
+use wrap::{Page, paginate};
 
 use chromiumoxide::browser::{Browser, BrowserConfig};
 use chromiumoxide::cdp::browser_protocol::page::{
@@ -12,12 +19,59 @@ use chromiumoxide::cdp::browser_protocol::page::{
 use futures::StreamExt;
 use tokio::runtime::Builder;
 
+/// How the entries are drawn, apart from the theme
+#[derive(Debug, Clone, Copy)]
+pub struct RenderOptions {
+    pub bake_font: bool,
+    pub color_by: Option<ColorBy>,
+    pub show_sizes: bool,
+    pub layout: Layout,
+    pub area_by: AreaBy,
+    /// Number of columns the list layout wraps into
+    pub columns: Option<u32>,
+    /// Rows per column the list layout wraps after
+    pub max_rows: Option<u32>,
+    /// Rows per page, each written to its own numbered file
+    pub page_rows: Option<u32>,
+}
+
 pub fn visualize(
     filestructure: Vec<FlatFsEntry>,
     theme: Theme,
     filetype: Filetype,
     output_filepath: PathBuf,
-    bake_font: bool,
+    options: RenderOptions,
+) {
+    let Some(page_rows) = options.page_rows else {
+        return build(
+            filestructure,
+            theme,
+            filetype,
+            output_filepath,
+            options,
+            None,
+        );
+    };
+    for page in paginate(&filestructure, page_rows) {
+        let page_filepath = page_filepath(&output_filepath, filetype.extension(), page.number);
+        build(
+            filestructure.clone(),
+            theme.clone(),
+            filetype.clone(),
+            page_filepath,
+            options,
+            Some(&page),
+        );
+    }
+}
+
+fn build(
+    filestructure: Vec<FlatFsEntry>,
+    theme: Theme,
+    filetype: Filetype,
+    output_filepath: PathBuf,
+    options: RenderOptions,
+    page: Option<&Page>,
 ) {
     match filetype {
         Filetype::SVG => build_svg(
@@ -25,15 +79,88 @@ pub fn visualize(
             theme,
             output_filepath,
             Filetype::SVG.extension(),
-            bake_font,
+            options,
+            page,
         ),
         Filetype::PNG => build_png(
             filestructure,
             theme,
             output_filepath,
             Filetype::PNG.extension(),
+            options,
+            page,
+        ),
+        Filetype::TXT => build_txt(
+            filestructure,
+            output_filepath,
+            Filetype::TXT.extension(),
+            options.show_sizes,
+            page,
         ),
+        Filetype::JSON => {
+            // Pages of a snapshot keep their ancestors, so every page is a valid tree
+            let entries = match page {
+                Some(page) => page_entries(&filestructure, page),
+                None => filestructure,
+            };
+            build_json(entries, output_filepath, Filetype::JSON.extension())
+        }
+    }
+}
+
+/// Path of a numbered page, e.g. `out-2.svg` for `out.svg` or `out`
+fn page_filepath(output_filepath: &Path, extension: &str, number: usize) -> PathBuf {
+    let stem = output_filepath
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = output_filepath
+        .extension()
+        .map(|extension| extension.to_string_lossy().into_owned())
+        .unwrap_or_else(|| extension.to_string());
+    output_filepath.with_file_name(format!("{}-{}.{}", stem, number, extension))
+}
+
+/// The entries shown on a page: its breadcrumbs followed by its rows
+fn page_entries(filestructure: &[FlatFsEntry], page: &Page) -> Vec<FlatFsEntry> {
+    page.segment
+        .breadcrumbs
+        .iter()
+        .copied()
+        .chain(page.segment.rows.clone())
+        .map(|i| filestructure[i].clone())
+        .collect()
+}
+
+/// Writes the entries as JSON. The output can be used as snapshot for `glimmr diff`.
+fn build_json(
+    filestructure: Vec<FlatFsEntry>,
+    mut output_filepath: PathBuf,
+    extension: &'static str,
+) {
+    let json = serde_json::to_string_pretty(&filestructure).unwrap();
+
+    if output_filepath.extension().is_none() {
+        output_filepath.set_extension(extension);
+    }
+    std::fs::write(&output_filepath, json).unwrap();
+    debug!("Saved JSON to {}", output_filepath.display());
+}
+
+fn build_txt(
+    filestructure: Vec<FlatFsEntry>,
+    mut output_filepath: PathBuf,
+    extension: &'static str,
+    show_sizes: bool,
+    page: Option<&Page>,
+) {
+    let text = compose_txt_from_filestruct(&filestructure, show_sizes, page);
+
+    if output_filepath.extension().is_none() {
+        output_filepath.set_extension(extension);
     }
+    std::fs::write(&output_filepath, text).unwrap();
+    debug!("Saved TXT to {}", output_filepath.display());
 }
 
 fn build_svg(
@@ -41,10 +168,11 @@ fn build_svg(
     theme: Theme,
     mut output_filepath: PathBuf,
     extension: &'static str,
-    bake_font: bool,
+    options: RenderOptions,
+    page: Option<&Page>,
 ) {
     // Compose svg
-    let document = compose_svg_from_filestruct(filestructure, theme, bake_font);
+    let document = compose_svg_from_filestruct(filestructure, theme, options, page);
 
     // Output
     debug!("Provided output_filepath: {}", output_filepath.display());
@@ -60,9 +188,15 @@ pub fn build_png(
     theme: Theme,
     mut output_filepath: PathBuf,
     extension: &'static str,
+    options: RenderOptions,
+    page: Option<&Page>,
 ) {
     // Compose SVG (always bake font for PNG rendering)
-    let document = compose_svg_from_filestruct(filestructure, theme, true);
+    let options = RenderOptions {
+        bake_font: true,
+        ..options
+    };
+    let document = compose_svg_from_filestruct(filestructure, theme, options, page);
     let svg_data = document.to_string();
 
     // Run Chromium in a Tokio runtime
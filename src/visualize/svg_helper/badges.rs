@@ -0,0 +1,221 @@
+use crate::args::themes::Theme;
+use crate::fs_parser::fs_structs::{Diagnostics, FlatFsEntry};
+use crate::visualize::svg_helper::fonts::text_width;
+use crate::visualize::svg_helper::heatmap::LegendRow;
+use rusttype::Font;
+use std::collections::BTreeSet;
+use svg::node::element::{Group, Rectangle, Text};
+
+const BADGE_GAP: u32 = 6;
+const BADGE_X_PADDING: u32 = 4;
+const UNOWNED_LABEL: &str = "unowned";
+
+/// A small labelled box drawn after the name of an entry, e.g. its coverage, a diagnostic
+/// count or an owner
+struct Badge<'a> {
+    label: String,
+    color: &'a String,
+    /// Filled badges stand out, outlined ones just tag the entry
+    filled: bool,
+}
+
+/// The badges of an entry, in the order they are drawn
+fn entry_badges<'a>(entry: &FlatFsEntry, theme: &'a Theme) -> Vec<Badge<'a>> {
+    let mut badges = Vec::new();
+    if let Some(ratio) = entry.coverage.and_then(|c| c.ratio()) {
+        badges.push(Badge {
+            label: format!("{:.0}%", ratio * 100.0),
+            color: coverage_color(ratio, theme),
+            filled: true,
+        });
+    }
+    if let Some(diagnostics) = entry.diagnostics {
+        for (count, color) in severity_counts(&diagnostics, theme) {
+            if count > 0 {
+                badges.push(Badge {
+                    label: count.to_string(),
+                    color,
+                    filled: true,
+                });
+            }
+        }
+    }
+    match &entry.owners {
+        Some(owners) if owners.is_empty() => badges.push(Badge {
+            label: UNOWNED_LABEL.to_string(),
+            color: &theme.unowned_color,
+            filled: true,
+        }),
+        Some(owners) => badges.extend(owners.iter().map(|owner| Badge {
+            label: owner.clone(),
+            color: owner_color(owner, theme),
+            filled: false,
+        })),
+        None => {}
+    }
+    badges
+}
+
+/// Picks the color of an owner from the theme's `owner_colors`. The color only depends on the
+/// name, so an owner keeps its color across runs and pictures.
+fn owner_color<'a>(owner: &str, theme: &'a Theme) -> &'a String {
+    if theme.owner_colors.is_empty() {
+        return &theme.unowned_color;
+    }
+    // FNV-1a, which unlike the std hasher is stable across Rust versions
+    let hash = owner.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    &theme.owner_colors[(hash % theme.owner_colors.len() as u64) as usize]
+}
+
+/// Picks the color of a coverage ratio from the theme's `coverage_colors`, which split the
+/// range from 0% to 100% into equal steps
+fn coverage_color(ratio: f64, theme: &Theme) -> &String {
+    let steps = theme.coverage_colors.len();
+    let index = ((ratio * steps as f64) as usize).min(steps.saturating_sub(1));
+    theme
+        .coverage_colors
+        .get(index)
+        .unwrap_or(&theme.annotation_text_color)
+}
+
+/// Diagnostic counts from most to least severe, with their colors
+fn severity_counts<'a>(diagnostics: &Diagnostics, theme: &'a Theme) -> [(u64, &'a String); 3] {
+    [
+        (diagnostics.errors, &theme.diagnostic_error_color),
+        (diagnostics.warnings, &theme.diagnostic_warning_color),
+        (diagnostics.notes, &theme.diagnostic_note_color),
+    ]
+}
+
+fn badge_width(label: &str, theme: &Theme, font: &Font) -> f32 {
+    text_width(font, theme.annotation_font_size, label) + (BADGE_X_PADDING * 2) as f32
+}
+
+/// Width the badges of an entry take up next to its label, including the gaps
+pub fn badges_width(entry: &FlatFsEntry, theme: &Theme, font: &Font) -> f32 {
+    entry_badges(entry, theme)
+        .iter()
+        .map(|badge| BADGE_GAP as f32 + badge_width(&badge.label, theme, font))
+        .sum()
+}
+
+/// Compose the badges of an entry, starting right of `x_pos`
+pub fn compose_badges(
+    entry: &FlatFsEntry,
+    x_pos: f32,
+    y_pos: u32,
+    theme: &Theme,
+    font: &Font,
+) -> Option<Group> {
+    let badges = entry_badges(entry, theme);
+    if badges.is_empty() {
+        return None;
+    }
+    let font_size = theme.annotation_font_size;
+    let mut group = Group::new()
+        .set("class", "badges")
+        .set("transform", format!("translate({},{})", x_pos, y_pos));
+
+    let mut x = 0.0;
+    for badge in badges {
+        x += BADGE_GAP as f32;
+        let width = badge_width(&badge.label, theme, font);
+        group = group
+            .add(
+                Rectangle::new()
+                    .set("x", x)
+                    .set("y", -(font_size as i32))
+                    .set("width", width)
+                    .set("height", font_size + 4)
+                    .set("rx", 3)
+                    .set("ry", 3)
+                    .set("fill", badge.color.clone())
+                    .set("fill-opacity", if badge.filled { 0.25 } else { 0.0 })
+                    .set("stroke", badge.color.clone()),
+            )
+            .add(
+                Text::new(badge.label)
+                    .set("x", x + BADGE_X_PADDING as f32)
+                    .set("font-family", theme.font.clone())
+                    .set("font-size", font_size)
+                    .set("fill", badge.color.clone()),
+            );
+        x += width;
+    }
+    Some(group)
+}
+
+/// Legend row for the coverage colors, if coverage is shown
+pub fn coverage_legend_rows<'a>(
+    filestructure: &[FlatFsEntry],
+    theme: &'a Theme,
+) -> Vec<LegendRow<'a>> {
+    if !filestructure.iter().any(|e| e.coverage.is_some()) {
+        return Vec::new();
+    }
+    vec![LegendRow {
+        label: "Lines".to_string(),
+        colors: &theme.coverage_colors,
+        start: "0%".to_string(),
+        end: "100%".to_string(),
+    }]
+}
+
+/// Legend rows for the severities that occur
+pub fn diagnostics_legend_rows<'a>(
+    filestructure: &[FlatFsEntry],
+    theme: &'a Theme,
+) -> Vec<LegendRow<'a>> {
+    let mut seen = Diagnostics::default();
+    for diagnostics in filestructure.iter().filter_map(|e| e.diagnostics) {
+        seen.errors = seen.errors.max(diagnostics.errors);
+        seen.warnings = seen.warnings.max(diagnostics.warnings);
+        seen.notes = seen.notes.max(diagnostics.notes);
+    }
+    ["Errors", "Warnings", "Notes"]
+        .into_iter()
+        .zip(severity_counts(&seen, theme))
+        .filter(|(_, (count, _))| *count > 0)
+        .map(|(label, (_, color))| LegendRow {
+            label: label.to_string(),
+            colors: std::slice::from_ref(color),
+            start: String::new(),
+            end: String::new(),
+        })
+        .collect()
+}
+
+/// Legend rows for every owner shown, sorted by name, and the unowned marker if it is used
+pub fn owner_legend_rows<'a>(
+    filestructure: &[FlatFsEntry],
+    theme: &'a Theme,
+) -> Vec<LegendRow<'a>> {
+    let owners: BTreeSet<&String> = filestructure
+        .iter()
+        .filter_map(|e| e.owners.as_ref())
+        .flatten()
+        .collect();
+    let mut rows: Vec<LegendRow> = owners
+        .into_iter()
+        .map(|owner| LegendRow {
+            label: owner.clone(),
+            colors: std::slice::from_ref(owner_color(owner, theme)),
+            start: String::new(),
+            end: String::new(),
+        })
+        .collect();
+    if filestructure
+        .iter()
+        .any(|e| e.owners.as_ref().is_some_and(Vec::is_empty))
+    {
+        rows.push(LegendRow {
+            label: UNOWNED_LABEL.to_string(),
+            colors: std::slice::from_ref(&theme.unowned_color),
+            start: String::new(),
+            end: String::new(),
+        });
+    }
+    rows
+}
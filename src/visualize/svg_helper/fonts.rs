@@ -2,6 +2,7 @@ use base64::{Engine, engine::general_purpose};
 use font_kit::handle::Handle;
 use font_kit::source::SystemSource;
 use log::debug;
+use rusttype::{Font, Scale, point};
 use std::fs;
 
 pub fn load_font_bytes(font_name: &str) -> Option<Vec<u8>> {
@@ -67,3 +68,15 @@ pub fn build_b64_font_embed(font_bytes: &[u8], font_family: &str) -> String {
         font_family, encoded
     )
 }
+
+/// Measure the advance width of `text` rendered with `font` at the given CSS font size
+pub fn text_width(font: &Font, font_size: u32, text: &str) -> f32 {
+    let v_metrics = font.v_metrics_unscaled();
+    let pixel_height =
+        font_size as f32 * (v_metrics.ascent - v_metrics.descent) / font.units_per_em() as f32;
+    font.layout(text, Scale::uniform(pixel_height), point(0.0, 0.0))
+        .last()
+        .map_or(0.0, |g| {
+            g.position().x + g.unpositioned().h_metrics().advance_width
+        })
+}
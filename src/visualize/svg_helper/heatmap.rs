@@ -0,0 +1,226 @@
+use crate::args::themes::Theme;
+use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType, Highlight};
+use crate::visualize::svg_helper::fonts::text_width;
+use rusttype::Font;
+use svg::node::element::{Group, Rectangle, Text};
+
+const SWATCH_WIDTH: u32 = 16;
+const SWATCH_HEIGHT: u32 = 12;
+const LEGEND_ROW_HEIGHT: u32 = 18;
+const LEGEND_GAP: u32 = 6;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// How an entry is colored by its history
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Heat<'a> {
+    /// Background color replacing the theme's
+    pub color: Option<&'a String>,
+    /// Whether the entry is rendered at `dim_opacity`
+    pub dimmed: bool,
+}
+
+impl Heat<'_> {
+    /// The highlight an entry is rendered with. Old entries are dimmed unless highlighted.
+    pub fn highlight(&self, entry: &FlatFsEntry) -> Highlight {
+        if self.dimmed && entry.highlight == Highlight::None {
+            Highlight::Dimmed
+        } else {
+            entry.highlight
+        }
+    }
+}
+
+/// One row of swatches in a legend, e.g. the scale for files. Empty ends are left out.
+pub struct LegendRow<'a> {
+    pub label: String,
+    pub colors: &'a [String],
+    pub start: String,
+    pub end: String,
+}
+
+/// Picks the background color of every entry from `gradient` by its churn. Files and folders
+/// are scaled separately, since folders sum up their contents. The scale is logarithmic, so a
+/// few very active files don't wash out the rest.
+///
+/// Returns the heat in the order of `filestructure` and the legend rows explaining it.
+pub fn churn_heat<'a>(
+    filestructure: &[FlatFsEntry],
+    gradient: &'a [String],
+) -> (Vec<Heat<'a>>, Vec<LegendRow<'a>>) {
+    let is_file = |e: &FlatFsEntry| matches!(e.entry_type, FsEntryType::File);
+    let max_of = |files: bool| {
+        filestructure
+            .iter()
+            .filter(|e| is_file(e) == files)
+            .filter_map(|e| e.churn)
+            .max()
+            .unwrap_or(0)
+    };
+    let (max_file, max_folder) = (max_of(true), max_of(false));
+
+    let heat = filestructure
+        .iter()
+        .map(|entry| {
+            let color = entry.churn.filter(|churn| *churn > 0).and_then(|churn| {
+                let max = if is_file(entry) { max_file } else { max_folder };
+                let fraction = (churn as f64).ln_1p() / (max as f64).ln_1p();
+                gradient.get((fraction * gradient.len().saturating_sub(1) as f64).round() as usize)
+            });
+            Heat {
+                color,
+                dimmed: false,
+            }
+        })
+        .collect();
+
+    // Without colors, nothing is colored and there is no scale to explain
+    let mut rows = Vec::new();
+    for (label, max) in [("Files", max_file), ("Folders", max_folder)] {
+        if max > 0 && !gradient.is_empty() {
+            rows.push(LegendRow {
+                label: label.to_string(),
+                colors: gradient,
+                start: "1".to_string(),
+                end: max.to_string(),
+            });
+        }
+    }
+    (heat, rows)
+}
+
+/// Picks the background color of every entry from the theme's `age_colors` by the time since
+/// it was last changed, relative to `now`. Each of the theme's `age_thresholds` moves on to the
+/// next color, and entries older than `age_dim_after` are dimmed.
+pub fn age_heat<'a>(
+    filestructure: &[FlatFsEntry],
+    theme: &'a Theme,
+    now: i64,
+) -> (Vec<Heat<'a>>, Vec<LegendRow<'a>>) {
+    let heat = filestructure
+        .iter()
+        .map(|entry| {
+            let Some(modified) = entry.modified else {
+                return Heat::default();
+            };
+            let age_days = ((now - modified) / SECONDS_PER_DAY).max(0);
+            let passed = theme
+                .age_thresholds
+                .iter()
+                .filter(|threshold| age_days >= **threshold as i64)
+                .count();
+            Heat {
+                color: theme
+                    .age_colors
+                    .get(passed.min(theme.age_colors.len().saturating_sub(1))),
+                dimmed: age_days > theme.age_dim_after as i64,
+            }
+        })
+        .collect();
+
+    let rows = vec![LegendRow {
+        label: "Age".to_string(),
+        colors: &theme.age_colors,
+        start: "today".to_string(),
+        end: match theme.age_thresholds.last() {
+            Some(days) => format!("{} days and older", days),
+            None => String::new(),
+        },
+    }];
+    (heat, rows)
+}
+
+/// Compose a legend with a title and rows of color swatches, each labelled at both ends
+pub fn compose_legend(
+    title: &str,
+    rows: &[LegendRow],
+    x_pos: u32,
+    y_pos: u32,
+    theme: &Theme,
+    font: &Font,
+) -> Group {
+    let text = |content: &str, x: f32, y: u32| {
+        Text::new(content)
+            .set("x", x)
+            .set("y", y)
+            .set("font-family", theme.font.clone())
+            .set("font-size", theme.annotation_font_size)
+            .set("fill", theme.annotation_text_color.clone())
+    };
+
+    let mut group = Group::new()
+        .set("class", "legend")
+        .set("transform", format!("translate({},{})", x_pos, y_pos))
+        .add(text(title, 0.0, 0));
+
+    // Align the swatches of all rows behind the widest row label
+    let label_width = rows
+        .iter()
+        .map(|row| text_width(font, theme.annotation_font_size, &row.label))
+        .fold(0.0, f32::max);
+    for (i, row) in rows.iter().enumerate() {
+        let y = LEGEND_ROW_HEIGHT * (i as u32 + 1);
+        let mut x = label_width + LEGEND_GAP as f32;
+        group = group.add(text(&row.label, 0.0, y));
+        if !row.start.is_empty() {
+            group = group.add(text(&row.start, x, y));
+            x += text_width(font, theme.annotation_font_size, &row.start) + LEGEND_GAP as f32;
+        }
+        for color in row.colors {
+            group = group.add(
+                Rectangle::new()
+                    .set("x", x)
+                    .set("y", y as i32 - SWATCH_HEIGHT as i32 + 2)
+                    .set("width", SWATCH_WIDTH)
+                    .set("height", SWATCH_HEIGHT)
+                    .set("fill", color.clone()),
+            );
+            x += SWATCH_WIDTH as f32;
+        }
+        if !row.end.is_empty() {
+            group = group.add(text(&row.end, x + LEGEND_GAP as f32, y));
+        }
+    }
+    group
+}
+
+/// Height a legend with `rows` rows takes up below its title
+pub fn legend_height(rows: usize) -> u32 {
+    LEGEND_ROW_HEIGHT * rows as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_churn(entry_type: FsEntryType, churn: u64) -> FlatFsEntry {
+        FlatFsEntry {
+            entry_type,
+            churn: Some(churn),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn churn_spans_the_gradient() {
+        let gradient: Vec<String> = ["cold", "warm", "hot"].map(String::from).to_vec();
+        let filestructure = [
+            with_churn(FsEntryType::File, 1),
+            with_churn(FsEntryType::File, 100),
+            with_churn(FsEntryType::File, 0),
+            with_churn(FsEntryType::Folder, 101),
+        ];
+        let (heat, rows) = churn_heat(&filestructure, &gradient);
+        let colors: Vec<Option<&str>> = heat.iter().map(|h| h.color.map(String::as_str)).collect();
+        assert_eq!(colors, [Some("cold"), Some("hot"), None, Some("hot")]);
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn empty_gradient_colors_nothing() {
+        let filestructure = [with_churn(FsEntryType::File, 5)];
+        let (heat, rows) = churn_heat(&filestructure, &[]);
+        assert!(heat[0].color.is_none());
+        assert!(rows.is_empty());
+    }
+}
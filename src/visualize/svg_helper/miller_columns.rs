@@ -0,0 +1,223 @@
+use crate::args::themes::Theme;
+use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType, Highlight};
+use crate::visualize::svg_helper::fonts::text_width;
+use crate::visualize::svg_helper::heatmap::Heat;
+use crate::visualize::svg_helper::nodes::compose_connector;
+use crate::visualize::svg_helper::tree::{TreeNode, build_tree};
+use crate::visualize::svg_helper::{
+    ITEM_BG_X_PADDING, ROW_HEIGHT, ROW_PADDING, compose_label, label_colors, label_width,
+    with_highlight_opacity,
+};
+use rusttype::Font;
+use svg::node::element::path::Data;
+use svg::node::element::{Group, Rectangle, Text};
+
+/// Space between the border of a column and its rows
+const PANEL_PADDING: f32 = 4.0;
+/// Space between columns, where the connectors run
+const COLUMN_GAP: f32 = 32.0;
+/// Space between two folders listed in the same column
+const PANEL_GAP: f32 = 12.0;
+const CHEVRON: &str = "›";
+
+/// The children of one folder, listed in a column
+struct Panel<'a, 'b> {
+    column: usize,
+    /// Row the panel expands, none for the top level entries
+    parent: Option<usize>,
+    nodes: &'a [TreeNode<'b>],
+    y: f32,
+}
+
+/// Compose the entries as Miller columns. The first column lists the top level entries, and
+/// every selected folder lists its children in the next column, next to it. Entries are
+/// selected with `--focus`, highlights only change their colors. With several selected folders
+/// in one column, their children are listed below each other.
+///
+/// Returns the columns and their width and height.
+pub fn compose_miller_columns(
+    filestructure: &[FlatFsEntry],
+    heat: &[Heat],
+    theme: &Theme,
+    font: &Font,
+) -> (Group, f32, f32) {
+    let roots = build_tree(filestructure);
+    let mut panels = vec![Panel {
+        column: 0,
+        parent: None,
+        nodes: &roots,
+        y: 0.0,
+    }];
+    collect_panels(&mut panels, &roots, 0);
+
+    // Every column is as wide as its widest row
+    let columns = panels.iter().map(|p| p.column + 1).max().unwrap_or(0);
+    let chevron_width = text_width(font, theme.folder_font_size, CHEVRON);
+    let mut row_widths = vec![0.0_f32; columns];
+    for panel in &panels {
+        for node in panel.nodes {
+            let width = label_width(node.entry, theme, font) + chevron_width;
+            row_widths[panel.column] = row_widths[panel.column].max(width);
+        }
+    }
+    for width in &mut row_widths {
+        *width += (ITEM_BG_X_PADDING * 3) as f32;
+    }
+    let mut column_x = Vec::with_capacity(columns);
+    let mut x = 0.0;
+    for width in &row_widths {
+        column_x.push(x);
+        x += width + PANEL_PADDING * 2.0 + COLUMN_GAP;
+    }
+    let width = (x - COLUMN_GAP).max(0.0);
+
+    // Panels start level with the row they expand, unless the column is taken there already
+    let row_pitch = (ROW_HEIGHT + ROW_PADDING) as f32;
+    let mut row_y = vec![0.0; filestructure.len()];
+    let mut column_bottom = vec![f32::NEG_INFINITY; columns];
+    for panel in &mut panels {
+        let wanted = panel.parent.map_or(0.0, |p| row_y[p] - PANEL_PADDING);
+        panel.y = wanted.max(column_bottom[panel.column] + PANEL_GAP);
+        for (i, node) in panel.nodes.iter().enumerate() {
+            row_y[node.index] = panel.y + PANEL_PADDING + row_pitch * i as f32;
+        }
+        column_bottom[panel.column] = panel.y + panel_height(panel);
+    }
+    let height = column_bottom.iter().copied().fold(0.0, f32::max);
+
+    let mut group = Group::new().set("class", "miller-columns");
+    for panel in &panels {
+        let panel_x = column_x[panel.column];
+        let row_width = row_widths[panel.column];
+        group = group.add(
+            Rectangle::new()
+                .set("class", "column")
+                .set("x", panel_x)
+                .set("y", panel.y)
+                .set("width", row_width + PANEL_PADDING * 2.0)
+                .set("height", panel_height(panel))
+                .set("rx", theme.folder_bg_corner_rad)
+                .set("ry", theme.folder_bg_corner_rad)
+                .set("fill", "none")
+                .set("stroke", theme.hierarchy_line_color.clone())
+                .set("stroke-width", 1),
+        );
+
+        // The connector runs from the expanded row to the first row of the panel
+        if let (Some(parent), Some(first)) = (panel.parent, panel.nodes.first()) {
+            let from_x = column_x[panel.column - 1] + PANEL_PADDING + row_widths[panel.column - 1];
+            let from_y = row_y[parent] + ROW_HEIGHT as f32 / 2.0;
+            let to_y = row_y[first.index] + ROW_HEIGHT as f32 / 2.0;
+            let turn = panel_x - COLUMN_GAP / 2.0;
+            let data = Data::new()
+                .move_to((from_x, from_y))
+                .line_to((turn, from_y))
+                .line_to((turn, to_y))
+                .line_to((panel_x, to_y));
+            group = group.add(compose_connector(data, Highlight::Path, theme));
+        }
+
+        for node in panel.nodes {
+            group = group.add(compose_row(
+                node.entry,
+                panel_x + PANEL_PADDING,
+                row_y[node.index],
+                row_width,
+                heat[node.index],
+                theme,
+            ));
+        }
+    }
+
+    (group, width, height)
+}
+
+/// Adds a panel for the children of every selected folder in `nodes`, followed by the panels
+/// below it, so the panels of a column are in the order of the tree
+fn collect_panels<'a, 'b>(
+    panels: &mut Vec<Panel<'a, 'b>>,
+    nodes: &'a [TreeNode<'b>],
+    column: usize,
+) {
+    for node in nodes {
+        if node.entry.selected && !node.children.is_empty() {
+            panels.push(Panel {
+                column: column + 1,
+                parent: Some(node.index),
+                nodes: &node.children,
+                y: 0.0,
+            });
+            collect_panels(panels, &node.children, column + 1);
+        }
+    }
+}
+
+/// Height of a panel with its padding
+fn panel_height(panel: &Panel) -> f32 {
+    let rows = panel.nodes.len() as f32;
+    PANEL_PADDING * 2.0 + ROW_HEIGHT as f32 * rows + ROW_PADDING as f32 * (rows - 1.0)
+}
+
+/// Compose the row of an entry. Selected entries use the highlight colors all along the path,
+/// folders show a chevron at the end.
+fn compose_row(
+    entry: &FlatFsEntry,
+    x: f32,
+    y: f32,
+    width: f32,
+    heat: Heat,
+    theme: &Theme,
+) -> Group {
+    let is_file = matches!(entry.entry_type, FsEntryType::File);
+    let (bg_color, text_color, font_size, corner_rad) = if is_file {
+        (
+            &theme.file_bg_color,
+            &theme.file_text_color,
+            theme.file_font_size,
+            theme.file_bg_corner_rad,
+        )
+    } else {
+        (
+            &theme.folder_bg_color,
+            &theme.folder_text_color,
+            theme.folder_font_size,
+            theme.folder_bg_corner_rad,
+        )
+    };
+    let (bg_color, text_color) = if entry.selected {
+        (&theme.highlight_bg_color, &theme.highlight_text_color)
+    } else {
+        label_colors(entry, heat.color.unwrap_or(bg_color), text_color, theme)
+    };
+    let baseline = (ROW_HEIGHT as f32 + font_size as f32 * 0.7) / 2.0;
+
+    let mut group = Group::new()
+        .set("class", "node")
+        .set("transform", format!("translate({},{})", x, y))
+        .add(
+            Rectangle::new()
+                .set("width", width)
+                .set("height", ROW_HEIGHT)
+                .set("rx", corner_rad)
+                .set("ry", corner_rad)
+                .set("fill", bg_color.clone()),
+        )
+        .add(compose_label(entry, font_size, text_color, theme).set("y", baseline));
+    if !is_file {
+        group = group.add(
+            Text::new(CHEVRON)
+                .set("x", width - ITEM_BG_X_PADDING as f32)
+                .set("y", baseline)
+                .set("text-anchor", "end")
+                .set("font-family", theme.font.clone())
+                .set("font-size", font_size)
+                .set("fill", text_color.clone()),
+        );
+    }
+    // Selected entries stay opaque, they lead to what is shown
+    let highlight = match heat.highlight(entry) {
+        Highlight::Dimmed if entry.selected => Highlight::None,
+        highlight => highlight,
+    };
+    with_highlight_opacity(group, highlight, theme)
+}
@@ -0,0 +1,238 @@
+use crate::args::themes::Theme;
+use crate::fs_parser::fs_structs::{FlatFsEntry, Highlight};
+use crate::visualize::svg_helper::heatmap::Heat;
+use crate::visualize::svg_helper::nodes::{
+    BOX_HEIGHT, box_width, compose_box, compose_connector, highlighted_last,
+};
+use crate::visualize::svg_helper::tree::{TreeNode, build_tree};
+use rusttype::Font;
+use svg::node::element::path::Data;
+use svg::node::element::{Circle, Group, Path};
+
+/// Horizontal space between a box and its children, where the connectors curve
+const LEVEL_GAP: f32 = 48.0;
+const SIBLING_GAP: f32 = 8.0;
+/// Radius of the dot the branches start from when there is no single root
+const HUB_RADIUS: f32 = 4.0;
+
+/// Which side of the root a branch grows to
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// Sizes and positions of all boxes of a mind map
+struct MindMap {
+    /// Width of each box, in the order of the flat list
+    widths: Vec<f32>,
+    /// Top left corner of each box, relative to the center of the root
+    positions: Vec<(f32, f32)>,
+    /// Side each box is on, the root is on neither
+    sides: Vec<Option<Side>>,
+}
+
+/// Compose a mind map of the entries. The root sits in the middle with the top level branches
+/// split between its right and left, so both sides are about equally tall. Deeper levels branch
+/// outward along curved connectors. Branches read clockwise: down the right side, then up the
+/// left side.
+///
+/// Returns the mind map and its width and height.
+pub fn compose_mind_map(
+    filestructure: &[FlatFsEntry],
+    heat: &[Heat],
+    theme: &Theme,
+    font: &Font,
+) -> (Group, f32, f32) {
+    let roots = build_tree(filestructure);
+    // A single top level folder is the root, otherwise the top level entries branch off a hub
+    let (root, branches) = match roots.as_slice() {
+        [root] if !root.children.is_empty() => (Some(root), root.children.as_slice()),
+        _ => (None, roots.as_slice()),
+    };
+
+    let mut map = MindMap {
+        widths: filestructure
+            .iter()
+            .map(|entry| box_width(entry, theme, font))
+            .collect(),
+        positions: vec![(0.0, 0.0); filestructure.len()],
+        sides: vec![None; filestructure.len()],
+    };
+    let half_root = root.map_or(HUB_RADIUS, |root| map.widths[root.index] / 2.0);
+    if let Some(root) = root {
+        map.positions[root.index] = (-half_root, -BOX_HEIGHT / 2.0);
+    }
+
+    let split = map.balanced_split(branches);
+    let (right, left) = branches.split_at(split);
+    let left: Vec<&TreeNode> = left.iter().rev().collect();
+    let right: Vec<&TreeNode> = right.iter().collect();
+    for (side, branches) in [(Side::Right, right), (Side::Left, left)] {
+        let edge = match side {
+            Side::Right => half_root + LEVEL_GAP,
+            Side::Left => -half_root - LEVEL_GAP,
+        };
+        let mut top = -map.stack_height(&branches) / 2.0;
+        for branch in branches {
+            map.place(branch, side, edge, top);
+            top += map.height(branch) + SIBLING_GAP;
+        }
+    }
+
+    // Shift everything so the map starts at the origin
+    let (mut min_x, mut min_y, mut max_x, mut max_y) =
+        (-half_root, -BOX_HEIGHT / 2.0, half_root, BOX_HEIGHT / 2.0);
+    for ((x, y), width) in map.positions.iter().zip(&map.widths) {
+        min_x = min_x.min(*x);
+        min_y = min_y.min(*y);
+        max_x = max_x.max(x + width);
+        max_y = max_y.max(y + BOX_HEIGHT);
+    }
+    for (x, y) in &mut map.positions {
+        *x -= min_x;
+        *y -= min_y;
+    }
+    let center = (-min_x, -min_y);
+
+    // Connectors go first, so boxes cover their ends
+    let mut group = Group::new().set("class", "mind-map");
+    for branch in highlighted_last(branches) {
+        let start = match (root, map.sides[branch.index]) {
+            (Some(root), Some(side)) => map.edge(root.index, side),
+            _ => center,
+        };
+        group = group.add(compose_curve(
+            start,
+            map.edge_facing(branch.index),
+            heat[branch.index].highlight(branch.entry),
+            theme,
+        ));
+        group = map.compose_connectors(group, branch, heat, theme);
+    }
+    if root.is_none() && !branches.is_empty() {
+        group = group.add(
+            Circle::new()
+                .set("cx", center.0)
+                .set("cy", center.1)
+                .set("r", HUB_RADIUS)
+                .set("fill", theme.hierarchy_line_color.clone()),
+        );
+    }
+    for (i, entry) in filestructure.iter().enumerate() {
+        let (x, y) = map.positions[i];
+        group = group.add(compose_box(entry, x, y, map.widths[i], heat[i], theme));
+    }
+
+    (group, max_x - min_x, max_y - min_y)
+}
+
+impl MindMap {
+    /// Height a subtree takes up
+    fn height(&self, node: &TreeNode) -> f32 {
+        let children: Vec<&TreeNode> = node.children.iter().collect();
+        self.stack_height(&children).max(BOX_HEIGHT)
+    }
+
+    /// Height of subtrees stacked on top of each other
+    fn stack_height(&self, nodes: &[&TreeNode]) -> f32 {
+        nodes.iter().map(|n| self.height(n)).sum::<f32>()
+            + SIBLING_GAP * nodes.len().saturating_sub(1) as f32
+    }
+
+    /// Number of branches that go to the right, so both sides are as equally tall as the order
+    /// of the branches allows. With an odd split the right side gets the extra branch.
+    fn balanced_split(&self, branches: &[TreeNode]) -> usize {
+        let heights: Vec<f32> = branches.iter().map(|b| self.height(b)).collect();
+        let total: f32 = heights.iter().sum();
+        let mut right = 0.0;
+        let mut best = (0, total);
+        for (i, height) in heights.iter().enumerate() {
+            right += height;
+            let difference = (total - right * 2.0).abs();
+            if difference <= best.1 {
+                best = (i + 1, difference);
+            }
+        }
+        best.0
+    }
+
+    /// Places a subtree with its box touching `edge` on the side facing the root and its top at
+    /// `top`. Parents are centered on their children.
+    fn place(&mut self, node: &TreeNode, side: Side, edge: f32, top: f32) {
+        let width = self.widths[node.index];
+        let x = match side {
+            Side::Right => edge,
+            Side::Left => edge - width,
+        };
+        let child_edge = match side {
+            Side::Right => x + width + LEVEL_GAP,
+            Side::Left => x - LEVEL_GAP,
+        };
+        let children: Vec<&TreeNode> = node.children.iter().collect();
+        let height = self.height(node);
+        let mut child_top = top + (height - self.stack_height(&children)) / 2.0;
+        for child in &children {
+            self.place(child, side, child_edge, child_top);
+            child_top += self.height(child) + SIBLING_GAP;
+        }
+
+        let center = match (node.children.first(), node.children.last()) {
+            (Some(first), Some(last)) => {
+                (self.positions[first.index].1 + self.positions[last.index].1 + BOX_HEIGHT) / 2.0
+            }
+            _ => top + height / 2.0,
+        };
+        self.positions[node.index] = (x, center - BOX_HEIGHT / 2.0);
+        self.sides[node.index] = Some(side);
+    }
+
+    /// Middle of the left or right edge of a box
+    fn edge(&self, index: usize, side: Side) -> (f32, f32) {
+        let (x, y) = self.positions[index];
+        match side {
+            Side::Right => (x + self.widths[index], y + BOX_HEIGHT / 2.0),
+            Side::Left => (x, y + BOX_HEIGHT / 2.0),
+        }
+    }
+
+    /// Middle of the edge of a box that faces the root
+    fn edge_facing(&self, index: usize) -> (f32, f32) {
+        match self.sides[index] {
+            Some(Side::Left) => self.edge(index, Side::Right),
+            _ => self.edge(index, Side::Left),
+        }
+    }
+
+    /// Compose the connectors from an entry to its children, and below them
+    fn compose_connectors(
+        &self,
+        mut group: Group,
+        node: &TreeNode,
+        heat: &[Heat],
+        theme: &Theme,
+    ) -> Group {
+        let Some(side) = self.sides[node.index] else {
+            return group;
+        };
+        for child in highlighted_last(&node.children) {
+            group = group.add(compose_curve(
+                self.edge(node.index, side),
+                self.edge_facing(child.index),
+                heat[child.index].highlight(child.entry),
+                theme,
+            ));
+            group = self.compose_connectors(group, child, heat, theme);
+        }
+        group
+    }
+}
+
+/// Compose a connector that leaves `from` and enters `to` horizontally, curving in between
+fn compose_curve(from: (f32, f32), to: (f32, f32), highlight: Highlight, theme: &Theme) -> Path {
+    let middle = (from.0 + to.0) / 2.0;
+    let data = Data::new()
+        .move_to(from)
+        .cubic_curve_to(((middle, from.1), (middle, to.1), to));
+    compose_connector(data, highlight, theme)
+}
@@ -1,11 +1,40 @@
+use crate::args::color_by::ColorBy;
+use crate::args::layout::Layout;
 use crate::args::themes::{HierarchyLineStyles, Theme};
-use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType};
-use crate::visualize::svg_helper::fonts::{build_b64_font_embed, load_font_bytes};
+use crate::fs_parser::fs_structs::{DiffStatus, FlatFsEntry, FsEntryType, GitStatus, Highlight};
+use crate::visualize::RenderOptions;
+use crate::visualize::columns::{MetricColumn, metric_columns};
+use crate::visualize::svg_helper::badges::{
+    badges_width, compose_badges, coverage_legend_rows, diagnostics_legend_rows, owner_legend_rows,
+};
+use crate::visualize::svg_helper::fonts::{build_b64_font_embed, load_font_bytes, text_width};
+use crate::visualize::svg_helper::heatmap::{
+    Heat, LegendRow, age_heat, churn_heat, compose_legend, legend_height,
+};
+use crate::visualize::svg_helper::miller_columns::compose_miller_columns;
+use crate::visualize::svg_helper::mind_map::compose_mind_map;
+use crate::visualize::svg_helper::nested_boxes::compose_nested_boxes;
+use crate::visualize::svg_helper::nodes::compose_connector;
+use crate::visualize::svg_helper::org_chart::compose_org_chart;
+use crate::visualize::svg_helper::sunburst::compose_sunburst;
+use crate::visualize::svg_helper::treemap::compose_treemap;
+use crate::visualize::wrap::{Page, wrap_rows};
+use chrono::Local;
 use rusttype::Font;
 use svg::Document;
 use svg::node::element::path::Data;
-use svg::node::element::{Group, Path, Rectangle, Script, Text};
+use svg::node::element::{Group, Path, Rectangle, Script, TSpan, Text};
+mod badges;
 mod fonts;
+mod heatmap;
+mod miller_columns;
+mod mind_map;
+mod nested_boxes;
+mod nodes;
+mod org_chart;
+mod sunburst;
+mod tree;
+mod treemap;
 use svg::Node;
 
 const ROW_HEIGHT: u32 = 20;
@@ -15,20 +44,27 @@ const TOP_PADDING: u32 = (ROW_HEIGHT as f32 * 1.5) as u32;
 const BG_X_PADDING: u32 = 20;
 const ITEM_BG_X_PADDING: u32 = 3;
 const ITEM_BG_Y_PADDING: u32 = 1;
+const ANNOTATION_GAP: u32 = 40;
+const METRIC_GAP: u32 = 16;
+/// Space between columns when the list wraps
+const WRAP_COLUMN_GAP: u32 = 40;
+/// Opacity of the ancestors repeated at the top of a wrapped column
+const BREADCRUMB_OPACITY: f32 = 0.6;
 
 /// Compose the full SVG from the folder structure
 pub fn compose_svg_from_filestruct(
     filestructure: Vec<FlatFsEntry>,
     theme: Theme,
-    bake_font: bool,
+    options: RenderOptions,
+    page: Option<&Page>,
 ) -> Document {
     let mut doc = Document::new();
 
     // Load font and embed into svg
     let font_bytes = load_font_bytes(&theme.font)
-        .expect(&format!("Failed to load system font '{}'", theme.font));
+        .unwrap_or_else(|| panic!("Failed to load system font '{}'", theme.font));
     let font = Font::try_from_vec(font_bytes.clone()).expect("Invalid font data");
-    if bake_font {
+    if options.bake_font {
         doc = doc.add(svg::node::element::Style::new(build_b64_font_embed(
             &font_bytes,
             &theme.font,
@@ -47,41 +83,291 @@ pub fn compose_svg_from_filestruct(
         doc = doc.add(background);
     }
 
-    // Build hierarchy lines
-    for (i, entry) in filestructure.iter().enumerate() {
-        // Build hierarchy lines for this row
-        for d in 0..entry.depth {
-            let mut is_last = false;
-            if i == filestructure.len() - 1 {
-                is_last = true;
-            } else if filestructure[i + 1].depth < d + 1 {
-                is_last = true
+    // Color the backgrounds by git history
+    let now = Local::now().timestamp();
+    let (heat, heat_legend_rows) = match options.color_by {
+        Some(ColorBy::Commits | ColorBy::Lines) => {
+            churn_heat(&filestructure, &theme.heatmap_colors)
+        }
+        Some(ColorBy::Age) => age_heat(&filestructure, &theme, now),
+        None => (vec![Heat::default(); filestructure.len()], Vec::new()),
+    };
+
+    // Other layouts replace the rows, the legends go below them
+    let other_layout = match options.layout {
+        Layout::List => None,
+        Layout::Treemap => Some(compose_treemap(
+            &filestructure,
+            &heat,
+            options.area_by,
+            &theme,
+            &font,
+        )),
+        Layout::Sunburst => Some(compose_sunburst(
+            &filestructure,
+            &heat,
+            options.area_by,
+            &theme,
+            &font,
+        )),
+        Layout::OrgChart | Layout::OrgChartHorizontal => Some(compose_org_chart(
+            &filestructure,
+            &heat,
+            options.layout == Layout::OrgChartHorizontal,
+            &theme,
+            &font,
+        )),
+        Layout::MindMap => Some(compose_mind_map(&filestructure, &heat, &theme, &font)),
+        Layout::NestedBoxes => Some(compose_nested_boxes(&filestructure, &heat, &theme, &font)),
+        Layout::MillerColumns => Some(compose_miller_columns(&filestructure, &heat, &theme, &font)),
+    };
+    if let Some((layout, width, height)) = other_layout {
+        doc = doc.add(layout.set(
+            "transform",
+            format!("translate({},{})", BG_X_PADDING, BG_X_PADDING),
+        ));
+        let layout_end = BG_X_PADDING + height.ceil() as u32;
+        let (doc, legend_space) = compose_legends(
+            doc,
+            &filestructure,
+            heat_legend_rows,
+            options.color_by,
+            layout_end,
+            &theme,
+            &font,
+        );
+        return doc.set(
+            "viewBox",
+            (
+                0,
+                0,
+                width.ceil() as u32 + BG_X_PADDING * 2,
+                layout_end + legend_space + BG_X_PADDING,
+            ),
+        );
+    }
+
+    // Metric columns get a header row above the entries
+    let columns = metric_columns(&filestructure, options.show_sizes);
+    let top_padding = if columns.is_empty() {
+        TOP_PADDING
+    } else {
+        TOP_PADDING + ROW_HEIGHT
+    };
+
+    // Measure the labels, metrics and annotations, which every wrapped column repeats
+    let labels_end = filestructure
+        .iter()
+        .map(|e| label_end(e, &theme, &font) + badges_width(e, &theme, &font))
+        .fold(0.0, f32::max);
+    let mut metric_ends = Vec::with_capacity(columns.len());
+    let mut metrics_end = labels_end;
+    for (c, column) in columns.iter().enumerate() {
+        let gap = if c == 0 { ANNOTATION_GAP } else { METRIC_GAP };
+        metrics_end += gap as f32 + metric_column_width(column, &theme, &font);
+        metric_ends.push(metrics_end);
+    }
+    let annotations_end = filestructure
+        .iter()
+        .filter_map(|e| e.annotation.as_ref())
+        .map(|a| text_width(&font, theme.annotation_font_size, a))
+        .reduce(f32::max)
+        .map(|column_width| metrics_end + ANNOTATION_GAP as f32 + column_width);
+    let content_width = annotations_end.unwrap_or(metrics_end) - BG_X_PADDING as f32;
+
+    // Tall lists wrap into columns side by side, or show one page of rows. Each column or page
+    // after the first starts with the ancestors of its first row, and hierarchy lines run on as
+    // if the rows were not split. Rows on other pages are left out.
+    let segments = match page {
+        Some(page) => vec![page.segment.clone()],
+        None => wrap_rows(&filestructure, options.columns, options.max_rows),
+    };
+    let column_offset = |s: usize| s as u32 * (content_width.ceil() as u32 + WRAP_COLUMN_GAP);
+    let mut row_x_offsets = vec![0; filestructure.len()];
+    let mut row_ys = vec![None; filestructure.len()];
+    let mut column_rows = 0;
+    let dim_active = filestructure
+        .iter()
+        .any(|e| e.highlight == Highlight::Dimmed);
+    for (s, segment) in segments.iter().enumerate() {
+        let x_offset = column_offset(s);
+        let shown: Vec<(usize, bool)> = segment
+            .breadcrumbs
+            .iter()
+            .map(|&i| (i, true))
+            .chain(segment.rows.clone().map(|i| (i, false)))
+            .collect();
+        column_rows = column_rows.max(shown.len());
+
+        for (position, &(i, is_breadcrumb)) in shown.iter().enumerate() {
+            let entry = &filestructure[i];
+            let row_y = (ROW_HEIGHT + ROW_PADDING) * position as u32 + top_padding;
+            if !is_breadcrumb {
+                row_x_offsets[i] = x_offset;
+                row_ys[i] = Some(row_y);
             }
 
-            doc = doc.add(compose_hierarchy_line(
-                DEPTH_OFFSET / 2 + DEPTH_OFFSET * d + BG_X_PADDING,
-                (ROW_HEIGHT + ROW_PADDING) * (i - 1) as u32 + TOP_PADDING,
-                is_last,
-                &theme,
-            ))
+            // Build hierarchy lines for this row. Breadcrumbs always continue below.
+            for d in 0..entry.depth {
+                let is_last = !is_breadcrumb
+                    && (i == filestructure.len() - 1 || filestructure[i + 1].depth < d + 1);
+
+                let line_highlight = if leads_to_highlight(&filestructure, i, d) {
+                    Highlight::Path
+                } else if dim_active {
+                    Highlight::Dimmed
+                } else {
+                    Highlight::None
+                };
+
+                doc = doc.add(compose_hierarchy_line(
+                    DEPTH_OFFSET / 2 + DEPTH_OFFSET * d + BG_X_PADDING + x_offset,
+                    row_y - (ROW_HEIGHT + ROW_PADDING),
+                    is_last,
+                    line_highlight,
+                    &theme,
+                ))
+            }
+
+            if is_breadcrumb {
+                // Left out ancestors are marked with an ellipsis before the first breadcrumb
+                let elided_entry;
+                let entry = if segment.elided && position == 0 {
+                    elided_entry = FlatFsEntry {
+                        name: format!("…/{}", entry.name),
+                        ..entry.clone()
+                    };
+                    &elided_entry
+                } else {
+                    entry
+                };
+                let row_x = DEPTH_OFFSET * entry.depth + BG_X_PADDING + x_offset;
+                let mut breadcrumb = Group::new()
+                    .set("class", "breadcrumb")
+                    .set("opacity", BREADCRUMB_OPACITY)
+                    .add(compose_folder(
+                        entry, row_x, row_y, heat[i], None, &theme, &font,
+                    ));
+                // The deepest breadcrumb is marked as continued from before
+                if position + 1 == segment.breadcrumbs.len() {
+                    let label_end = label_end(entry, &theme, &font) + x_offset as f32;
+                    breadcrumb = breadcrumb.add(
+                        Text::new("continued")
+                            .set("x", label_end + ITEM_BG_X_PADDING as f32 * 2.0)
+                            .set("y", row_y)
+                            .set("font-family", theme.font.clone())
+                            .set("font-size", theme.annotation_font_size)
+                            .set("font-style", "italic")
+                            .set("fill", theme.annotation_text_color.clone()),
+                    );
+                }
+                doc = doc.add(breadcrumb);
+            }
         }
     }
 
+    let shares = if options.show_sizes {
+        size_shares(&filestructure)
+    } else {
+        vec![None; filestructure.len()]
+    };
+
     // Build filestructure visualization
     for (i, entry) in filestructure.iter().enumerate() {
-        // Build file/folder for this row
+        let Some(row_y) = row_ys[i] else {
+            continue;
+        };
+        // Build file/folder for this row. With sizes, a bar behind the label spans its share
+        // of the parent's size, measured against the space up to the widest label.
+        let x_offset = row_x_offsets[i];
         let row_x = DEPTH_OFFSET * entry.depth + BG_X_PADDING;
-        let row_y = (ROW_HEIGHT + ROW_PADDING) * i as u32 + TOP_PADDING;
+        let bar_width = shares[i].map(|share| share as f32 * (labels_end - row_x as f32));
         match entry.entry_type {
             FsEntryType::File => {
-                doc = doc.add(compose_file(&entry.name, row_x, row_y, &theme, &font))
+                doc = doc.add(compose_file(
+                    entry,
+                    row_x + x_offset,
+                    row_y,
+                    heat[i],
+                    bar_width,
+                    &theme,
+                    &font,
+                ))
             }
-            FsEntryType::Folder => {
-                doc = doc.add(compose_folder(&entry.name, row_x, row_y, &theme, &font))
+            FsEntryType::Folder | FsEntryType::Submodule => {
+                doc = doc.add(compose_folder(
+                    entry,
+                    row_x + x_offset,
+                    row_y,
+                    heat[i],
+                    bar_width,
+                    &theme,
+                    &font,
+                ))
+            }
+        }
+        let label_end = label_end(entry, &theme, &font) + x_offset as f32;
+        if let Some(badges) = compose_badges(entry, label_end, row_y, &theme, &font) {
+            doc = doc.add(badges);
+        }
+    }
+
+    // Build right-aligned metric columns next to the widest label, with headers in every
+    // wrapped column
+    for (column, metric_end) in columns.iter().zip(&metric_ends) {
+        for s in 0..segments.len() {
+            doc = doc.add(compose_metric(
+                column.header,
+                metric_end + column_offset(s) as f32,
+                top_padding - ROW_HEIGHT,
+                Highlight::None,
+                &theme,
+            ));
+        }
+        for (i, (entry, value)) in filestructure.iter().zip(&column.values).enumerate() {
+            if let (Some(value), Some(row_y)) = (value, row_ys[i]) {
+                doc = doc.add(compose_metric(
+                    value,
+                    metric_end + row_x_offsets[i] as f32,
+                    row_y,
+                    entry.highlight,
+                    &theme,
+                ));
             }
         }
     }
 
+    // Build right-aligned annotation column after the labels and metrics
+    if let Some(column_end) = annotations_end {
+        for (i, entry) in filestructure.iter().enumerate() {
+            if let (Some(annotation), Some(row_y)) = (&entry.annotation, row_ys[i]) {
+                doc = doc.add(compose_annotation(
+                    annotation,
+                    column_end + row_x_offsets[i] as f32,
+                    row_y,
+                    entry.highlight,
+                    &theme,
+                ));
+            }
+        }
+    }
+
+    // Build the page indicator and legends below the longest column
+    let mut rows_height = column_rows as u32 * (ROW_HEIGHT + ROW_PADDING) + top_padding;
+    if let Some(page) = page {
+        doc = doc.add(compose_page_indicator(page, rows_height, &theme));
+        rows_height += ROW_HEIGHT + ROW_PADDING;
+    }
+    let (mut doc, legend_space) = compose_legends(
+        doc,
+        &filestructure,
+        heat_legend_rows,
+        options.color_by,
+        rows_height,
+        &theme,
+        &font,
+    );
+
     // Add script to get widths and heights correct
     let script_content = format!(
         r#"
@@ -102,7 +388,7 @@ pub fn compose_svg_from_filestruct(
     function adjustViewBox() {{
         const svg = document.querySelector('svg');
         let maxWidth = 0;
-        document.querySelectorAll('g.file, g.folder').forEach(group => {{
+        document.querySelectorAll('g.file, g.folder, g.annotation, g.metric, g.legend, g.badges').forEach(group => {{
             const groupBBox = group.getBBox();
             const transform = group.transform.baseVal.consolidate().matrix;
             const groupWidth = groupBBox.x + transform.e + groupBBox.width;
@@ -130,61 +416,397 @@ pub fn compose_svg_from_filestruct(
     doc = doc.add(script);
 
     // Define SVG size. Width will be dynamically set by js script
-    let computed_height = filestructure.len() as u32 * (ROW_HEIGHT + ROW_PADDING) + TOP_PADDING;
+    let computed_height = rows_height + legend_space;
     doc = doc.set("viewBox", (0, 0, 100, computed_height));
 
     doc
 }
 
+/// Compose the legends of the colors and badges in use, stacked below `y_pos`. Returns the
+/// document and the height the legends take up.
+fn compose_legends(
+    mut doc: Document,
+    filestructure: &[FlatFsEntry],
+    heat_legend_rows: Vec<LegendRow>,
+    color_by: Option<ColorBy>,
+    y_pos: u32,
+    theme: &Theme,
+    font: &Font,
+) -> (Document, u32) {
+    let heat_title = match color_by {
+        Some(ColorBy::Lines) => "Changed lines",
+        Some(ColorBy::Age) => "Last changed",
+        _ => "Commits",
+    };
+    let legends = [
+        (heat_title, heat_legend_rows),
+        ("Coverage", coverage_legend_rows(filestructure, theme)),
+        ("Diagnostics", diagnostics_legend_rows(filestructure, theme)),
+        ("Owners", owner_legend_rows(filestructure, theme)),
+    ];
+    let mut legend_space = 0;
+    for (title, rows) in legends.iter().filter(|(_, rows)| !rows.is_empty()) {
+        doc = doc.add(compose_legend(
+            title,
+            rows,
+            BG_X_PADDING,
+            y_pos + legend_space + ROW_HEIGHT,
+            theme,
+            font,
+        ));
+        legend_space += ROW_HEIGHT * 2 + legend_height(rows.len());
+    }
+    (doc, legend_space)
+}
+
+/// Share of each entry in the size of its parent. Top level entries share the size of all top
+/// level entries.
+fn size_shares(filestructure: &[FlatFsEntry]) -> Vec<Option<f64>> {
+    let top_level_total: u64 = filestructure
+        .iter()
+        .filter(|e| e.depth == 0)
+        .filter_map(|e| e.size)
+        .sum();
+    // Size of the last entry seen at each depth, i.e. of the ancestors of the current entry
+    let mut ancestor_sizes: Vec<u64> = Vec::new();
+    filestructure
+        .iter()
+        .map(|entry| {
+            let depth = entry.depth as usize;
+            ancestor_sizes.truncate(depth);
+            let total = match depth {
+                0 => top_level_total,
+                _ => ancestor_sizes.get(depth - 1).copied().unwrap_or(0),
+            };
+            ancestor_sizes.push(entry.size.unwrap_or(0));
+            let size = entry.size?;
+            (total > 0).then(|| size as f64 / total as f64)
+        })
+        .collect()
+}
+
+/// Returns whether the hierarchy line segment at `column` in `row` leads to a highlighted entry,
+/// i.e. whether a highlighted child of the parent at depth `column` follows at or below `row`.
+fn leads_to_highlight(filestructure: &[FlatFsEntry], row: usize, column: u32) -> bool {
+    filestructure[row..]
+        .iter()
+        .take_while(|e| e.depth > column)
+        .filter(|e| e.depth == column + 1)
+        .any(|e| matches!(e.highlight, Highlight::Match | Highlight::Path))
+}
+
 /// Compose a file SVG element
-fn compose_file(name: &str, x_pos: u32, y_pos: u32, theme: &Theme, _font: &Font) -> Group {
+fn compose_file(
+    entry: &FlatFsEntry,
+    x_pos: u32,
+    y_pos: u32,
+    heat: Heat,
+    bar_width: Option<f32>,
+    theme: &Theme,
+    _font: &Font,
+) -> Group {
+    let bg_color = heat.color.unwrap_or(&theme.file_bg_color);
+    let (bg_color, text_color) = label_colors(entry, bg_color, &theme.file_text_color, theme);
+
     let bg = Rectangle::new()
         .set("class", "label-bg")
         .set("y", -(theme.file_font_size as i32))
         .set("height", ROW_HEIGHT)
         .set("rx", theme.file_bg_corner_rad)
         .set("ry", theme.file_bg_corner_rad)
-        .set("fill", theme.file_bg_color.clone());
+        .set("fill", bg_color.clone());
 
-    let text = Text::new(name)
-        .set("class", "label-text")
-        .set("x", ITEM_BG_X_PADDING)
-        .set("font-family", theme.font.clone())
-        .set("font-size", theme.file_font_size)
-        .set("fill", theme.file_text_color.clone());
+    let text = compose_label(entry, theme.file_font_size, text_color, theme);
 
-    Group::new()
+    let mut group = Group::new()
         .set("class", "file")
         .set("transform", format!("translate({},{})", x_pos, y_pos))
-        .add(bg)
-        .add(text)
+        .add(bg);
+    if let Some(width) = bar_width {
+        group = group.add(compose_size_bar(
+            width,
+            theme.file_font_size,
+            theme.file_bg_corner_rad,
+            theme,
+        ));
+    }
+    with_highlight_opacity(group.add(text), heat.highlight(entry), theme)
 }
 
-/// Compose a folder SVG element
-fn compose_folder(name: &str, x_pos: u32, y_pos: u32, theme: &Theme, _font: &Font) -> Group {
-    let bg = Rectangle::new()
+/// Compose a folder SVG element. Folders that hide their children get a trailing marker,
+/// submodules a dashed outline.
+fn compose_folder(
+    entry: &FlatFsEntry,
+    x_pos: u32,
+    y_pos: u32,
+    heat: Heat,
+    bar_width: Option<f32>,
+    theme: &Theme,
+    _font: &Font,
+) -> Group {
+    let bg_color = heat.color.unwrap_or(&theme.folder_bg_color);
+    let (bg_color, text_color) = label_colors(entry, bg_color, &theme.folder_text_color, theme);
+
+    let mut bg = Rectangle::new()
         .set("class", "label-bg")
         .set("y", -(theme.folder_font_size as i32))
         .set("height", ROW_HEIGHT)
         .set("rx", theme.folder_bg_corner_rad)
         .set("ry", theme.folder_bg_corner_rad)
-        .set("fill", theme.folder_bg_color.clone());
+        .set("fill", bg_color.clone());
+    let is_submodule = matches!(entry.entry_type, FsEntryType::Submodule);
+    if is_submodule {
+        bg = bg
+            .set("stroke", text_color.clone())
+            .set("stroke-dasharray", "3 2");
+    }
+
+    let text = compose_label(entry, theme.folder_font_size, text_color, theme);
 
-    let text = Text::new(name)
+    let class = if is_submodule {
+        "folder submodule"
+    } else if entry.hides_children {
+        "folder collapsed"
+    } else {
+        "folder"
+    };
+    let mut group = Group::new()
+        .set("class", class)
+        .set("transform", format!("translate({},{})", x_pos, y_pos))
+        .add(bg);
+    if let Some(width) = bar_width {
+        group = group.add(compose_size_bar(
+            width,
+            theme.folder_font_size,
+            theme.folder_bg_corner_rad,
+            theme,
+        ));
+    }
+    with_highlight_opacity(group.add(text), heat.highlight(entry), theme)
+}
+
+/// Compose the bar behind a label that shows the share of an entry in its parent's size.
+/// It is translucent, so a heat color below stays visible.
+fn compose_size_bar(width: f32, font_size: u32, corner_rad: u32, theme: &Theme) -> Rectangle {
+    Rectangle::new()
+        .set("class", "size-bar")
+        .set("y", -(font_size as i32))
+        .set("width", width)
+        .set("height", ROW_HEIGHT)
+        .set("rx", corner_rad)
+        .set("ry", corner_rad)
+        .set("fill", theme.size_bar_color.clone())
+        .set("fill-opacity", 0.7)
+}
+
+/// Compose the label text of an entry, including its prefix and suffix markers
+fn compose_label(entry: &FlatFsEntry, font_size: u32, text_color: &str, theme: &Theme) -> Text {
+    let mut text = Text::new("")
         .set("class", "label-text")
         .set("x", ITEM_BG_X_PADDING)
         .set("font-family", theme.font.clone())
-        .set("font-size", theme.folder_font_size)
-        .set("fill", theme.folder_text_color.clone());
+        .set("font-size", font_size)
+        .set("fill", text_color);
+    if entry.diff == Some(DiffStatus::Removed) {
+        text = text.set("text-decoration", "line-through");
+    }
+
+    let prefix = label_prefix(entry);
+    if !prefix.is_empty() {
+        text = text.add(TSpan::new(prefix).set("font-weight", "bold"));
+    }
+    text = text.add(svg::node::Text::new(entry.name.clone()));
+    let suffix = label_suffix(entry);
+    if !suffix.is_empty() {
+        text = text.add(TSpan::new(suffix).set("opacity", 0.6));
+    }
+    if let Some((marker, color)) = git_status_marker(entry, theme) {
+        text = text.add(
+            TSpan::new(marker)
+                .set("class", "git-status")
+                .set("font-weight", "bold")
+                .set("fill", color.clone()),
+        );
+    }
+    text
+}
+
+/// Status letter shown after the label with `--git-status`, and its color
+fn git_status_marker<'a>(
+    entry: &FlatFsEntry,
+    theme: &'a Theme,
+) -> Option<(&'static str, &'a String)> {
+    match entry.git_status? {
+        GitStatus::Untracked => Some((" ?", &theme.git_untracked_color)),
+        GitStatus::Staged => Some((" S", &theme.git_staged_color)),
+        GitStatus::Modified => Some((" M", &theme.git_modified_color)),
+        GitStatus::Conflicted => Some((" U", &theme.git_conflicted_color)),
+    }
+}
+
+/// Background and text color of a label. Highlights take precedence over diff colors.
+fn label_colors<'a>(
+    entry: &FlatFsEntry,
+    bg_color: &'a String,
+    text_color: &'a String,
+    theme: &'a Theme,
+) -> (&'a String, &'a String) {
+    if entry.highlight == Highlight::Match {
+        return (&theme.highlight_bg_color, &theme.highlight_text_color);
+    }
+    let text_color = match entry.diff {
+        Some(DiffStatus::Added) => &theme.diff_added_color,
+        Some(DiffStatus::Removed) => &theme.diff_removed_color,
+        Some(DiffStatus::Modified | DiffStatus::Renamed(_)) => &theme.diff_changed_color,
+        Some(DiffStatus::Unchanged) | None => text_color,
+    };
+    (bg_color, text_color)
+}
+
+/// Compose an annotation, right-aligned to `x_end`
+fn compose_annotation(
+    annotation: &str,
+    x_end: f32,
+    y_pos: u32,
+    highlight: Highlight,
+    theme: &Theme,
+) -> Group {
+    let text = Text::new(annotation)
+        .set("text-anchor", "end")
+        .set("font-family", theme.font.clone())
+        .set("font-size", theme.annotation_font_size)
+        .set("fill", theme.annotation_text_color.clone());
+
+    with_highlight_opacity(
+        Group::new()
+            .set("class", "annotation")
+            .set("transform", format!("translate({},{})", x_end, y_pos))
+            .add(text),
+        highlight,
+        theme,
+    )
+}
 
+/// Compose the number of the page and the page count, e.g. `Page 2 of 5`
+fn compose_page_indicator(page: &Page, y_pos: u32, theme: &Theme) -> Group {
     Group::new()
-        .set("class", "folder")
-        .set("transform", format!("translate({},{})", x_pos, y_pos))
-        .add(bg)
-        .add(text)
+        .set("class", "page-indicator")
+        .set(
+            "transform",
+            format!("translate({},{})", BG_X_PADDING, y_pos),
+        )
+        .add(
+            Text::new(format!("Page {} of {}", page.number, page.count))
+                .set("font-family", theme.font.clone())
+                .set("font-size", theme.annotation_font_size)
+                .set("fill", theme.annotation_text_color.clone()),
+        )
+}
+
+/// Compose a value or header of a metric column, right-aligned to `x_end`
+fn compose_metric(
+    value: &str,
+    x_end: f32,
+    y_pos: u32,
+    highlight: Highlight,
+    theme: &Theme,
+) -> Group {
+    let text = Text::new(value)
+        .set("text-anchor", "end")
+        .set("font-family", theme.font.clone())
+        .set("font-size", theme.annotation_font_size)
+        .set("fill", theme.annotation_text_color.clone());
+
+    with_highlight_opacity(
+        Group::new()
+            .set("class", "metric")
+            .set("transform", format!("translate({},{})", x_end, y_pos))
+            .add(text),
+        highlight,
+        theme,
+    )
+}
+
+/// Width of the widest value or header of a metric column
+fn metric_column_width(column: &MetricColumn, theme: &Theme, font: &Font) -> f32 {
+    column
+        .values
+        .iter()
+        .flatten()
+        .map(String::as_str)
+        .chain([column.header])
+        .map(|value| text_width(font, theme.annotation_font_size, value))
+        .fold(0.0, f32::max)
+}
+
+/// Estimate where the label of an entry ends horizontally, using the font metrics
+fn label_end(entry: &FlatFsEntry, theme: &Theme, font: &Font) -> f32 {
+    let row_x = DEPTH_OFFSET * entry.depth + BG_X_PADDING;
+    row_x as f32 + (ITEM_BG_X_PADDING * 2) as f32 + label_width(entry, theme, font)
+}
+
+/// Width of the label text of an entry, including its prefix and suffix markers
+fn label_width(entry: &FlatFsEntry, theme: &Theme, font: &Font) -> f32 {
+    let font_size = match entry.entry_type {
+        FsEntryType::File => theme.file_font_size,
+        FsEntryType::Folder | FsEntryType::Submodule => theme.folder_font_size,
+    };
+    let marker = git_status_marker(entry, theme).map_or("", |(marker, _)| marker);
+    let label = format!(
+        "{}{}{}{}",
+        label_prefix(entry),
+        entry.name,
+        label_suffix(entry),
+        marker
+    );
+    text_width(font, font_size, &label)
+}
+
+/// Text shown before the name: the diff marker
+fn label_prefix(entry: &FlatFsEntry) -> &'static str {
+    match entry.diff {
+        Some(DiffStatus::Added) => "+ ",
+        Some(DiffStatus::Removed) => "− ",
+        Some(DiffStatus::Modified) => "~ ",
+        Some(DiffStatus::Renamed(_)) => "» ",
+        Some(DiffStatus::Unchanged) | None => "",
+    }
+}
+
+/// Text shown after the name: a marker for hidden children, the target of a link, the
+/// commit of a submodule or where a renamed file came from
+fn label_suffix(entry: &FlatFsEntry) -> String {
+    let mut suffix = String::new();
+    if entry.hides_children {
+        suffix.push_str("/…");
+    }
+    if let Some(DiffStatus::Renamed(from)) = &entry.diff {
+        suffix.push_str(&format!(" ← {}", from.display()));
+    }
+    if let Some(target) = &entry.link_target {
+        match entry.entry_type {
+            FsEntryType::Submodule => suffix.push_str(&format!(" @ {}", target)),
+            _ => suffix.push_str(&format!(" → {}", target)),
+        }
+    }
+    suffix
+}
+
+/// Reduces the opacity of dimmed entries
+fn with_highlight_opacity(group: Group, highlight: Highlight, theme: &Theme) -> Group {
+    match highlight {
+        Highlight::Dimmed => group.set("opacity", theme.dim_opacity),
+        _ => group,
+    }
 }
 
-pub fn compose_hierarchy_line(x_pos: u32, y_pos: u32, is_last: bool, theme: &Theme) -> Path {
+pub fn compose_hierarchy_line(
+    x_pos: u32,
+    y_pos: u32,
+    is_last: bool,
+    highlight: Highlight,
+    theme: &Theme,
+) -> Path {
     match theme.hierarchy_line {
         HierarchyLineStyles::Default => {
             let mut data = Data::new().move_to((x_pos, y_pos));
@@ -198,13 +820,7 @@ pub fn compose_hierarchy_line(x_pos: u32, y_pos: u32, is_last: bool, theme: &The
                 data = data.vertical_line_by(ROW_HEIGHT + ROW_PADDING);
             }
 
-            let path = Path::new()
-                .set("fill", "none")
-                .set("stroke", theme.hierarchy_line_color.clone())
-                .set("stroke-width", 1)
-                .set("d", data);
-
-            return path;
+            compose_connector(data, highlight, theme)
         }
     }
 }
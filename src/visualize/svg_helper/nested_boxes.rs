@@ -0,0 +1,179 @@
+use crate::args::themes::Theme;
+use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType};
+use crate::visualize::svg_helper::heatmap::Heat;
+use crate::visualize::svg_helper::nodes::{BOX_HEIGHT, BOX_X_PADDING, compose_box};
+use crate::visualize::svg_helper::tree::{TreeNode, build_tree};
+use crate::visualize::svg_helper::{
+    compose_label, label_colors, label_width, with_highlight_opacity,
+};
+use rusttype::Font;
+use svg::node::element::{Group, Rectangle};
+
+/// How much wider than tall the children of a container are arranged
+const ASPECT_RATIO: f32 = 2.0;
+
+/// Sizes and positions of all boxes of the nested-box layout
+struct NestedBoxes<'a> {
+    theme: &'a Theme,
+    /// Width of the label of each entry, in the order of the flat list
+    label_widths: Vec<f32>,
+    sizes: Vec<(f32, f32)>,
+    /// Top left corner of each box, relative to its container
+    offsets: Vec<(f32, f32)>,
+}
+
+/// Compose the entries as nested boxes. Every folder is a container with its name in a header
+/// and its children flowing in rows inside it. Files are boxes like in the org chart.
+///
+/// Returns the boxes and their width and height.
+pub fn compose_nested_boxes(
+    filestructure: &[FlatFsEntry],
+    heat: &[Heat],
+    theme: &Theme,
+    font: &Font,
+) -> (Group, f32, f32) {
+    let roots = build_tree(filestructure);
+    let mut boxes = NestedBoxes {
+        theme,
+        label_widths: filestructure
+            .iter()
+            .map(|entry| label_width(entry, theme, font))
+            .collect(),
+        sizes: vec![(0.0, 0.0); filestructure.len()],
+        offsets: vec![(0.0, 0.0); filestructure.len()],
+    };
+    let (width, height) = boxes.flow(&roots);
+
+    let mut group = Group::new().set("class", "nested-boxes");
+    for root in &roots {
+        let (x, y) = boxes.offsets[root.index];
+        group = boxes.compose(group, root, x, y, heat);
+    }
+    (group, width, height)
+}
+
+impl NestedBoxes<'_> {
+    /// Measures an entry and everything inside it
+    fn measure(&mut self, node: &TreeNode) -> (f32, f32) {
+        let size = match node.entry.entry_type {
+            FsEntryType::File => (
+                self.label_widths[node.index] + BOX_X_PADDING * 2.0,
+                BOX_HEIGHT,
+            ),
+            FsEntryType::Folder | FsEntryType::Submodule => {
+                let padding = self.theme.container_padding as f32;
+                let header = self.theme.container_header_height as f32;
+                let (content_width, content_height) = self.flow(&node.children);
+                for child in &node.children {
+                    let (x, y) = self.offsets[child.index];
+                    self.offsets[child.index] = (x + padding, y + header);
+                }
+                let label_width = self.label_widths[node.index] + padding * 2.0;
+                if node.children.is_empty() {
+                    (label_width, header)
+                } else {
+                    (
+                        label_width.max(content_width + padding * 2.0),
+                        header + content_height + padding,
+                    )
+                }
+            }
+        };
+        self.sizes[node.index] = size;
+        size
+    }
+
+    /// Arranges `nodes` in rows, wrapping them so the rows come out about twice as wide as they
+    /// are tall. Children are spaced by the container padding.
+    ///
+    /// Returns the width and height of the rows.
+    fn flow(&mut self, nodes: &[TreeNode]) -> (f32, f32) {
+        let gap = self.theme.container_padding as f32;
+        let sizes: Vec<(f32, f32)> = nodes.iter().map(|n| self.measure(n)).collect();
+        let area: f32 = sizes.iter().map(|(w, h)| (w + gap) * (h + gap)).sum();
+        let widest = sizes.iter().map(|(w, _)| *w).fold(0.0, f32::max);
+        let target_width = widest.max((area * ASPECT_RATIO).sqrt());
+
+        let (mut x, mut y, mut row_height, mut width) = (0.0, 0.0, 0.0, 0.0_f32);
+        for (node, (w, h)) in nodes.iter().zip(sizes) {
+            if x > 0.0 && x + w > target_width {
+                x = 0.0;
+                y += row_height + gap;
+                row_height = 0.0;
+            }
+            self.offsets[node.index] = (x, y);
+            width = width.max(x + w);
+            row_height = f32::max(row_height, h);
+            x += w + gap;
+        }
+        (width, y + row_height)
+    }
+
+    /// Compose the box of an entry at `x` and `y`, followed by what it contains
+    fn compose(&self, mut group: Group, node: &TreeNode, x: f32, y: f32, heat: &[Heat]) -> Group {
+        let entry = node.entry;
+        let (width, height) = self.sizes[node.index];
+        if matches!(entry.entry_type, FsEntryType::File) {
+            return group.add(compose_box(
+                entry,
+                x,
+                y,
+                width,
+                heat[node.index],
+                self.theme,
+            ));
+        }
+
+        group = group.add(self.compose_container(entry, x, y, width, height, heat[node.index]));
+        for child in &node.children {
+            let (child_x, child_y) = self.offsets[child.index];
+            group = self.compose(group, child, x + child_x, y + child_y, heat);
+        }
+        group
+    }
+
+    /// Compose the rectangle of a folder with its name in the header. Its children are drawn
+    /// after it rather than inside its group, so dimming a folder doesn't dim them as well.
+    fn compose_container(
+        &self,
+        entry: &FlatFsEntry,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        heat: Heat,
+    ) -> Group {
+        let theme = self.theme;
+        let (bg_color, text_color) = label_colors(
+            entry,
+            heat.color.unwrap_or(&theme.folder_bg_color),
+            &theme.folder_text_color,
+            theme,
+        );
+        let mut bg = Rectangle::new()
+            .set("width", width)
+            .set("height", height)
+            .set("rx", theme.folder_bg_corner_rad)
+            .set("ry", theme.folder_bg_corner_rad)
+            .set("fill", bg_color.clone())
+            .set("stroke", theme.hierarchy_line_color.clone())
+            .set("stroke-width", 1);
+        if matches!(entry.entry_type, FsEntryType::Submodule) {
+            bg = bg.set("stroke-dasharray", "3 2");
+        }
+        let header = theme.container_header_height as f32;
+        let text = compose_label(entry, theme.folder_font_size, text_color, theme)
+            .set("x", theme.container_padding)
+            .set("y", (header + theme.folder_font_size as f32 * 0.7) / 2.0);
+
+        with_highlight_opacity(
+            Group::new()
+                .set("class", "container")
+                .set("transform", format!("translate({},{})", x, y))
+                .add(bg)
+                .add(text),
+            heat.highlight(entry),
+            theme,
+        )
+    }
+}
@@ -0,0 +1,95 @@
+use crate::args::themes::Theme;
+use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType, Highlight};
+use crate::visualize::svg_helper::heatmap::Heat;
+use crate::visualize::svg_helper::tree::TreeNode;
+use crate::visualize::svg_helper::{
+    ROW_HEIGHT, compose_label, label_colors, label_width, with_highlight_opacity,
+};
+use rusttype::Font;
+use svg::node::element::path::Data;
+use svg::node::element::{Group, Path, Rectangle};
+
+pub const BOX_X_PADDING: f32 = 8.0;
+pub const BOX_HEIGHT: f32 = (ROW_HEIGHT + 8) as f32;
+
+/// Width of the box of an entry, sized to its label
+pub fn box_width(entry: &FlatFsEntry, theme: &Theme, font: &Font) -> f32 {
+    label_width(entry, theme, font) + BOX_X_PADDING * 2.0
+}
+
+/// Compose the box of an entry with its label
+pub fn compose_box(
+    entry: &FlatFsEntry,
+    x: f32,
+    y: f32,
+    width: f32,
+    heat: Heat,
+    theme: &Theme,
+) -> Group {
+    let (bg_color, text_color, font_size, corner_rad) = match entry.entry_type {
+        FsEntryType::File => (
+            &theme.file_bg_color,
+            &theme.file_text_color,
+            theme.file_font_size,
+            theme.file_bg_corner_rad,
+        ),
+        FsEntryType::Folder | FsEntryType::Submodule => (
+            &theme.folder_bg_color,
+            &theme.folder_text_color,
+            theme.folder_font_size,
+            theme.folder_bg_corner_rad,
+        ),
+    };
+    let (bg_color, text_color) =
+        label_colors(entry, heat.color.unwrap_or(bg_color), text_color, theme);
+
+    let mut bg = Rectangle::new()
+        .set("width", width)
+        .set("height", BOX_HEIGHT)
+        .set("rx", corner_rad)
+        .set("ry", corner_rad)
+        .set("fill", bg_color.clone());
+    if matches!(entry.entry_type, FsEntryType::Submodule) {
+        bg = bg
+            .set("stroke", text_color.clone())
+            .set("stroke-dasharray", "3 2");
+    }
+    let text = compose_label(entry, font_size, text_color, theme)
+        .set("x", BOX_X_PADDING)
+        .set("y", (BOX_HEIGHT + font_size as f32 * 0.7) / 2.0);
+
+    with_highlight_opacity(
+        Group::new()
+            .set("class", "node")
+            .set("transform", format!("translate({},{})", x, y))
+            .add(bg)
+            .add(text),
+        heat.highlight(entry),
+        theme,
+    )
+}
+
+/// Compose a connector along `data`, emphasized when it leads to a highlighted entry
+pub fn compose_connector(data: Data, highlight: Highlight, theme: &Theme) -> Path {
+    let path = Path::new().set("fill", "none").set("d", data);
+    match highlight {
+        Highlight::Match | Highlight::Path => path
+            .set("stroke", theme.highlight_line_color.clone())
+            .set("stroke-width", 2),
+        Highlight::Dimmed => path
+            .set("stroke", theme.hierarchy_line_color.clone())
+            .set("stroke-width", 1)
+            .set("opacity", theme.dim_opacity),
+        Highlight::None => path
+            .set("stroke", theme.hierarchy_line_color.clone())
+            .set("stroke-width", 1),
+    }
+}
+
+/// Children in the order their connectors are drawn. Highlighted connectors come last, so they
+/// stay on top where connectors overlap.
+pub fn highlighted_last<'a, 'b>(children: &'a [TreeNode<'b>]) -> Vec<&'a TreeNode<'b>> {
+    let mut children: Vec<&TreeNode> = children.iter().collect();
+    children.sort_by_key(|c| matches!(c.entry.highlight, Highlight::Match | Highlight::Path));
+    children
+}
@@ -0,0 +1,190 @@
+use crate::args::themes::Theme;
+use crate::fs_parser::fs_structs::FlatFsEntry;
+use crate::visualize::svg_helper::heatmap::Heat;
+use crate::visualize::svg_helper::nodes::{
+    BOX_HEIGHT, box_width, compose_box, compose_connector, highlighted_last,
+};
+use crate::visualize::svg_helper::tree::{TreeNode, build_tree};
+use rusttype::Font;
+use svg::node::element::Group;
+use svg::node::element::path::Data;
+
+/// Space between a level and the next, where the connectors run
+const LEVEL_GAP: f32 = 40.0;
+const SIBLING_GAP: f32 = 12.0;
+
+/// Where a box is placed. The main axis runs from parents to children, the cross axis along
+/// siblings. For top-down charts that is down and right, for horizontal ones right and down.
+#[derive(Debug, Clone, Copy, Default)]
+struct Placement {
+    main: f32,
+    cross: f32,
+}
+
+/// Sizes and positions of all boxes of an org chart
+struct OrgChart {
+    horizontal: bool,
+    /// Width of each box, in the order of the flat list
+    widths: Vec<f32>,
+    /// Main axis position of each level
+    level_offsets: Vec<f32>,
+    placements: Vec<Placement>,
+}
+
+/// Compose an org chart of the entries. Every entry is a box sized to its label, with its
+/// children centered below it, or to the right of it if `horizontal` is set. Parents and
+/// children are joined by orthogonal connectors.
+///
+/// Returns the chart and its width and height.
+pub fn compose_org_chart(
+    filestructure: &[FlatFsEntry],
+    heat: &[Heat],
+    horizontal: bool,
+    theme: &Theme,
+    font: &Font,
+) -> (Group, f32, f32) {
+    let roots = build_tree(filestructure);
+    let widths: Vec<f32> = filestructure
+        .iter()
+        .map(|entry| box_width(entry, theme, font))
+        .collect();
+
+    // Every level is as deep as its deepest box on the main axis
+    let mut level_sizes: Vec<f32> = Vec::new();
+    for (entry, width) in filestructure.iter().zip(&widths) {
+        let level = entry.depth as usize;
+        if level_sizes.len() <= level {
+            level_sizes.resize(level + 1, 0.0);
+        }
+        let size = if horizontal { *width } else { BOX_HEIGHT };
+        level_sizes[level] = level_sizes[level].max(size);
+    }
+    let mut level_offsets = Vec::with_capacity(level_sizes.len());
+    let mut offset = 0.0;
+    for size in &level_sizes {
+        level_offsets.push(offset);
+        offset += size + LEVEL_GAP;
+    }
+    let main_extent = (offset - LEVEL_GAP).max(0.0);
+
+    let mut chart = OrgChart {
+        horizontal,
+        widths,
+        level_offsets,
+        placements: vec![Placement::default(); filestructure.len()],
+    };
+    let mut cross = 0.0;
+    for root in &roots {
+        chart.place(root, cross);
+        cross += chart.breadth(root) + SIBLING_GAP;
+    }
+    let cross_extent = (cross - SIBLING_GAP).max(0.0);
+
+    // Connectors go first, so boxes cover their ends
+    let mut group = Group::new().set("class", "org-chart");
+    for root in &roots {
+        group = chart.compose_connectors(group, root, heat, theme);
+    }
+    for (i, entry) in filestructure.iter().enumerate() {
+        let placement = chart.placements[i];
+        let (x, y) = chart.point(placement.main, placement.cross);
+        group = group.add(compose_box(entry, x, y, chart.widths[i], heat[i], theme));
+    }
+
+    if horizontal {
+        (group, main_extent, cross_extent)
+    } else {
+        (group, cross_extent, main_extent)
+    }
+}
+
+impl OrgChart {
+    /// Size of a box on the cross axis
+    fn cross_size(&self, index: usize) -> f32 {
+        if self.horizontal {
+            BOX_HEIGHT
+        } else {
+            self.widths[index]
+        }
+    }
+
+    /// Size of a box on the main axis
+    fn main_size(&self, index: usize) -> f32 {
+        if self.horizontal {
+            self.widths[index]
+        } else {
+            BOX_HEIGHT
+        }
+    }
+
+    /// Space a subtree takes up on the cross axis
+    fn breadth(&self, node: &TreeNode) -> f32 {
+        let children: f32 = node.children.iter().map(|c| self.breadth(c)).sum::<f32>()
+            + SIBLING_GAP * node.children.len().saturating_sub(1) as f32;
+        children.max(self.cross_size(node.index))
+    }
+
+    /// Places a subtree starting at `cross` on the cross axis. Parents are centered on their
+    /// children.
+    fn place(&mut self, node: &TreeNode, cross: f32) {
+        let breadth = self.breadth(node);
+        let children_breadth: f32 = node.children.iter().map(|c| self.breadth(c)).sum::<f32>()
+            + SIBLING_GAP * node.children.len().saturating_sub(1) as f32;
+        let mut child_cross = cross + (breadth - children_breadth) / 2.0;
+        for child in &node.children {
+            self.place(child, child_cross);
+            child_cross += self.breadth(child) + SIBLING_GAP;
+        }
+
+        let center = match (node.children.first(), node.children.last()) {
+            (Some(first), Some(last)) => (self.center(first) + self.center(last)) / 2.0,
+            _ => cross + breadth / 2.0,
+        };
+        self.placements[node.index] = Placement {
+            main: self.level_offsets[node.entry.depth as usize],
+            cross: center - self.cross_size(node.index) / 2.0,
+        };
+    }
+
+    /// Center of a placed box on the cross axis
+    fn center(&self, node: &TreeNode) -> f32 {
+        self.placements[node.index].cross + self.cross_size(node.index) / 2.0
+    }
+
+    /// Turns main and cross axis coordinates into x and y
+    fn point(&self, main: f32, cross: f32) -> (f32, f32) {
+        if self.horizontal {
+            (main, cross)
+        } else {
+            (cross, main)
+        }
+    }
+
+    /// Compose the connectors from a parent to each of its children, and below them. They
+    /// leave the parent, turn halfway between the levels and enter the child.
+    fn compose_connectors(
+        &self,
+        mut group: Group,
+        node: &TreeNode,
+        heat: &[Heat],
+        theme: &Theme,
+    ) -> Group {
+        let parent_end = self.placements[node.index].main + self.main_size(node.index);
+        for child in highlighted_last(&node.children) {
+            let child_start = self.placements[child.index].main;
+            let turn = child_start - LEVEL_GAP / 2.0;
+            let data = Data::new()
+                .move_to(self.point(parent_end, self.center(node)))
+                .line_to(self.point(turn, self.center(node)))
+                .line_to(self.point(turn, self.center(child)))
+                .line_to(self.point(child_start, self.center(child)));
+            group = group.add(compose_connector(
+                data,
+                heat[child.index].highlight(child.entry),
+                theme,
+            ));
+            group = self.compose_connectors(group, child, heat, theme);
+        }
+        group
+    }
+}
@@ -0,0 +1,255 @@
+use crate::args::area_by::AreaBy;
+use crate::args::themes::Theme;
+use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType};
+use crate::visualize::svg_helper::fonts::text_width;
+use crate::visualize::svg_helper::heatmap::Heat;
+use crate::visualize::svg_helper::tree::{TreeNode, build_tree};
+use crate::visualize::svg_helper::{label_colors, with_highlight_opacity};
+use rusttype::Font;
+use std::f64::consts::{PI, TAU};
+use svg::node::element::path::Data;
+use svg::node::element::{Circle, Definitions, Group, Path, Text, TextPath, Title};
+
+const CENTER_RADIUS: f64 = 60.0;
+const RING_WIDTH: f64 = 48.0;
+const LABEL_PADDING: f64 = 6.0;
+/// Arcs can't start and end at the same point, so a full ring leaves out a sliver this wide
+const FULL_RING_GAP: f64 = 1e-4;
+
+/// What every sector of the sunburst needs to know
+struct Sunburst<'a> {
+    heat: &'a [Heat<'a>],
+    area_by: AreaBy,
+    theme: &'a Theme,
+    font: &'a Font<'a>,
+    /// Coordinate of the center on both axes
+    center: f64,
+}
+
+/// Compose a sunburst of the entries. The root sits in the center and every level below it is
+/// a ring, with the angle of an entry proportional to its weight. Labels follow the arcs where
+/// they fit.
+///
+/// Returns the sunburst and its width and height.
+pub fn compose_sunburst(
+    filestructure: &[FlatFsEntry],
+    heat: &[Heat],
+    area_by: AreaBy,
+    theme: &Theme,
+    font: &Font,
+) -> (Group, f32, f32) {
+    let roots = build_tree(filestructure);
+    // A single top level folder is the root, otherwise the top level entries form the first ring
+    let (root, ring_nodes) = match roots.as_slice() {
+        [root] if !root.children.is_empty() => (Some(root), root.children.as_slice()),
+        _ => (None, roots.as_slice()),
+    };
+    let rings = ring_nodes.iter().map(rings_below).max().unwrap_or(0);
+    let radius = CENTER_RADIUS + rings as f64 * RING_WIDTH;
+    let sunburst = Sunburst {
+        heat,
+        area_by,
+        theme,
+        font,
+        center: radius,
+    };
+
+    let mut group = Group::new().set("class", "sunburst");
+    if let Some(root) = root {
+        group = group.add(compose_center(root, &sunburst));
+    }
+    group = compose_ring(group, ring_nodes, 0, -PI / 2.0, TAU, &sunburst);
+    (group, (radius * 2.0) as f32, (radius * 2.0) as f32)
+}
+
+/// Number of rings an entry and everything below it take up
+fn rings_below(node: &TreeNode) -> usize {
+    1 + node.children.iter().map(rings_below).max().unwrap_or(0)
+}
+
+/// Splits the angle from `start` to `start + span` between `nodes` by weight and adds their
+/// sectors in `ring`, followed by the rings of their children
+fn compose_ring(
+    mut group: Group,
+    nodes: &[TreeNode],
+    ring: usize,
+    start: f64,
+    span: f64,
+    sunburst: &Sunburst,
+) -> Group {
+    let weights: Vec<f64> = nodes.iter().map(|n| n.weight(sunburst.area_by)).collect();
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return group;
+    }
+    let mut angle = start;
+    for (node, weight) in nodes.iter().zip(weights) {
+        let node_span = span * weight / total;
+        if node_span > 0.0 {
+            group = group.add(compose_sector(node, ring, angle, node_span, sunburst));
+            group = compose_ring(group, &node.children, ring + 1, angle, node_span, sunburst);
+        }
+        angle += node_span;
+    }
+    group
+}
+
+/// Compose the sector of an entry, with its label along the arc if it fits
+fn compose_sector(
+    node: &TreeNode,
+    ring: usize,
+    start: f64,
+    span: f64,
+    sunburst: &Sunburst,
+) -> Group {
+    let span = span.min(TAU - FULL_RING_GAP);
+    let inner = CENTER_RADIUS + ring as f64 * RING_WIDTH;
+    let outer = inner + RING_WIDTH;
+    let (bg_color, text_color, font_size) = node_style(node, sunburst);
+
+    let large_arc = if span > PI { 1.0 } else { 0.0 };
+    let end = start + span;
+    let (outer_start, outer_end) = (sunburst.point(outer, start), sunburst.point(outer, end));
+    let (inner_start, inner_end) = (sunburst.point(inner, start), sunburst.point(inner, end));
+    let data = Data::new()
+        .move_to(outer_start)
+        .elliptical_arc_to((outer, outer, 0.0, large_arc, 1.0, outer_end.0, outer_end.1))
+        .line_to(inner_end)
+        .elliptical_arc_to((
+            inner,
+            inner,
+            0.0,
+            large_arc,
+            0.0,
+            inner_start.0,
+            inner_start.1,
+        ))
+        .close();
+
+    let mut group = Group::new()
+        .set("class", "sector")
+        .add(Title::new(node.tooltip(sunburst.area_by)))
+        .add(
+            Path::new()
+                .set("d", data)
+                .set("fill", bg_color.clone())
+                .set("stroke", sector_border(sunburst.theme).clone())
+                .set("stroke-width", 1),
+        );
+
+    // Labels run along the middle of the ring. Below the center they run the other way, so
+    // they are not upside down. Labels that don't fit are left out, the tooltip names the entry.
+    let entry = node.entry;
+    let middle = (inner + outer) / 2.0;
+    let label_width = text_width(sunburst.font, font_size, &entry.name) as f64;
+    if label_width + LABEL_PADDING * 2.0 <= middle * span {
+        let id = format!("arc-{}", node.index);
+        let is_below = (start + span / 2.0).sin() > 0.0;
+        let baseline_shift = font_size as f64 * 0.35;
+        let label_arc = if is_below {
+            let radius = middle + baseline_shift;
+            let (from, to) = (sunburst.point(radius, end), sunburst.point(radius, start));
+            Data::new()
+                .move_to(from)
+                .elliptical_arc_to((radius, radius, 0.0, large_arc, 0.0, to.0, to.1))
+        } else {
+            let radius = middle - baseline_shift;
+            let (from, to) = (sunburst.point(radius, start), sunburst.point(radius, end));
+            Data::new()
+                .move_to(from)
+                .elliptical_arc_to((radius, radius, 0.0, large_arc, 1.0, to.0, to.1))
+        };
+        group = group
+            .add(Definitions::new().add(Path::new().set("id", id.clone()).set("d", label_arc)))
+            .add(
+                Text::new("")
+                    .set("text-anchor", "middle")
+                    .set("font-family", sunburst.theme.font.clone())
+                    .set("font-size", font_size)
+                    .set("fill", text_color.clone())
+                    .add(
+                        TextPath::new(entry.name.clone())
+                            .set("href", format!("#{}", id))
+                            .set("startOffset", "50%"),
+                    ),
+            );
+    }
+
+    with_highlight_opacity(
+        group,
+        sunburst.heat[node.index].highlight(entry),
+        sunburst.theme,
+    )
+}
+
+/// Compose the circle in the middle for the root
+fn compose_center(root: &TreeNode, sunburst: &Sunburst) -> Group {
+    let (bg_color, text_color, font_size) = node_style(root, sunburst);
+    let mut group = Group::new()
+        .set("class", "sector")
+        .add(Title::new(root.tooltip(sunburst.area_by)))
+        .add(
+            Circle::new()
+                .set("cx", sunburst.center)
+                .set("cy", sunburst.center)
+                .set("r", CENTER_RADIUS)
+                .set("fill", bg_color.clone())
+                .set("stroke", sector_border(sunburst.theme).clone())
+                .set("stroke-width", 1),
+        );
+    let label_width = text_width(sunburst.font, font_size, &root.entry.name) as f64;
+    if label_width + LABEL_PADDING * 2.0 <= CENTER_RADIUS * 2.0 {
+        group = group.add(
+            Text::new(root.entry.name.clone())
+                .set("x", sunburst.center)
+                .set("y", sunburst.center + font_size as f64 * 0.35)
+                .set("text-anchor", "middle")
+                .set("font-family", sunburst.theme.font.clone())
+                .set("font-size", font_size)
+                .set("fill", text_color.clone()),
+        );
+    }
+    with_highlight_opacity(
+        group,
+        sunburst.heat[root.index].highlight(root.entry),
+        sunburst.theme,
+    )
+}
+
+/// Background color, text color and font size of an entry
+fn node_style<'a>(node: &TreeNode, sunburst: &Sunburst<'a>) -> (&'a String, &'a String, u32) {
+    let theme = sunburst.theme;
+    let (bg_color, text_color, font_size) = match node.entry.entry_type {
+        FsEntryType::File => (
+            &theme.file_bg_color,
+            &theme.file_text_color,
+            theme.file_font_size,
+        ),
+        FsEntryType::Folder | FsEntryType::Submodule => (
+            &theme.folder_bg_color,
+            &theme.folder_text_color,
+            theme.folder_font_size,
+        ),
+    };
+    let bg_color = sunburst.heat[node.index].color.unwrap_or(bg_color);
+    let (bg_color, text_color) = label_colors(node.entry, bg_color, text_color, theme);
+    (bg_color, text_color, font_size)
+}
+
+/// Sectors are separated by lines in the background color
+fn sector_border(theme: &Theme) -> &String {
+    theme
+        .bg_color
+        .as_ref()
+        .unwrap_or(&theme.hierarchy_line_color)
+}
+
+impl Sunburst<'_> {
+    /// The point at `radius` from the center in the direction of `angle`
+    fn point(&self, radius: f64, angle: f64) -> (f64, f64) {
+        (
+            self.center + radius * angle.cos(),
+            self.center + radius * angle.sin(),
+        )
+    }
+}
@@ -0,0 +1,71 @@
+use crate::args::area_by::AreaBy;
+use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType};
+use crate::sizes::format_size;
+
+/// An entry with its children, for layouts that need the hierarchy instead of rows
+pub struct TreeNode<'a> {
+    /// Position in the flat list, to look up data kept per row like the heat
+    pub index: usize,
+    pub entry: &'a FlatFsEntry,
+    pub children: Vec<TreeNode<'a>>,
+}
+
+impl TreeNode<'_> {
+    /// Value the area of the entry is proportional to. Folders sum up their children, so
+    /// nested areas add up. Folders without shown children fall back to their own total.
+    pub fn weight(&self, area_by: AreaBy) -> f64 {
+        if !self.children.is_empty() {
+            return self.children.iter().map(|c| c.weight(area_by)).sum();
+        }
+        match area_by {
+            AreaBy::Files => 1.0,
+            AreaBy::Size => self.entry.size.unwrap_or(0) as f64,
+            AreaBy::Loc => self.entry.loc.map_or(0, |loc| loc.code) as f64,
+        }
+    }
+
+    /// Tooltip of the entry: its path and weight
+    pub fn tooltip(&self, area_by: AreaBy) -> String {
+        let path = if self.entry.path.as_os_str().is_empty() {
+            self.entry.name.clone()
+        } else {
+            self.entry.path.display().to_string()
+        };
+        let weight = self.weight(area_by);
+        let value = match area_by {
+            AreaBy::Files if matches!(self.entry.entry_type, FsEntryType::File) => {
+                return path;
+            }
+            AreaBy::Files if weight == 1.0 => "1 file".to_string(),
+            AreaBy::Files => format!("{} files", weight),
+            AreaBy::Size => format_size(weight as u64),
+            AreaBy::Loc => format!("{} lines of code", weight),
+        };
+        format!("{}\n{}", path, value)
+    }
+}
+
+/// Builds the trees below the top level entries of a flat list. Each entry becomes a child of
+/// the closest entry before it with a smaller depth.
+pub fn build_tree(filestructure: &[FlatFsEntry]) -> Vec<TreeNode<'_>> {
+    build_subtrees(filestructure, 0)
+}
+
+fn build_subtrees(entries: &[FlatFsEntry], offset: usize) -> Vec<TreeNode<'_>> {
+    let Some(depth) = entries.iter().map(|e| e.depth).min() else {
+        return Vec::new();
+    };
+    let mut nodes = Vec::new();
+    let mut start = 0;
+    for i in 1..=entries.len() {
+        if i == entries.len() || entries[i].depth <= depth {
+            nodes.push(TreeNode {
+                index: offset + start,
+                entry: &entries[start],
+                children: build_subtrees(&entries[start + 1..i], offset + start + 1),
+            });
+            start = i;
+        }
+    }
+    nodes
+}
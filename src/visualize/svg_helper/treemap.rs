@@ -0,0 +1,238 @@
+use crate::args::area_by::AreaBy;
+use crate::args::themes::Theme;
+use crate::fs_parser::fs_structs::{FlatFsEntry, FsEntryType};
+use crate::visualize::svg_helper::fonts::text_width;
+use crate::visualize::svg_helper::heatmap::Heat;
+use crate::visualize::svg_helper::tree::{TreeNode, build_tree};
+use crate::visualize::svg_helper::{label_colors, with_highlight_opacity};
+use rusttype::Font;
+use svg::node::element::{Group, Rectangle, Text, Title};
+
+const TREEMAP_WIDTH: f64 = 1200.0;
+const TREEMAP_HEIGHT: f64 = 800.0;
+/// Height of the band at the top of a folder that holds its name
+const HEADER_HEIGHT: f64 = 20.0;
+/// Space between the border of a folder and its children
+const FOLDER_PADDING: f64 = 3.0;
+const LABEL_PADDING: f64 = 4.0;
+
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Compose a squarified treemap of the entries. Every entry is a rectangle with an area
+/// proportional to its weight, folders contain their children below a header with their name.
+///
+/// Returns the treemap and its width and height.
+pub fn compose_treemap(
+    filestructure: &[FlatFsEntry],
+    heat: &[Heat],
+    area_by: AreaBy,
+    theme: &Theme,
+    font: &Font,
+) -> (Group, f32, f32) {
+    let roots = build_tree(filestructure);
+    let canvas = Rect {
+        x: 0.0,
+        y: 0.0,
+        width: TREEMAP_WIDTH,
+        height: TREEMAP_HEIGHT,
+    };
+    let group = compose_tiles(
+        Group::new().set("class", "treemap"),
+        &roots,
+        canvas,
+        heat,
+        area_by,
+        theme,
+        font,
+    );
+    (group, TREEMAP_WIDTH as f32, TREEMAP_HEIGHT as f32)
+}
+
+/// Lays out `nodes` in `rect` and adds their tiles to `group`. Entries without weight are
+/// left out.
+fn compose_tiles(
+    mut group: Group,
+    nodes: &[TreeNode],
+    rect: Rect,
+    heat: &[Heat],
+    area_by: AreaBy,
+    theme: &Theme,
+    font: &Font,
+) -> Group {
+    let mut weighted: Vec<(&TreeNode, f64)> = nodes
+        .iter()
+        .map(|node| (node, node.weight(area_by)))
+        .filter(|(_, weight)| *weight > 0.0)
+        .collect();
+    weighted.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let weights: Vec<f64> = weighted.iter().map(|(_, weight)| *weight).collect();
+
+    for ((node, _), tile) in weighted.iter().zip(squarify(&weights, rect)) {
+        group = group.add(compose_tile(node, tile, heat, area_by, theme, font));
+    }
+    group
+}
+
+/// Compose the tile of an entry. Folders get a header with their name and their children
+/// inside, if there is room for them.
+fn compose_tile(
+    node: &TreeNode,
+    tile: Rect,
+    heat: &[Heat],
+    area_by: AreaBy,
+    theme: &Theme,
+    font: &Font,
+) -> Group {
+    let entry = node.entry;
+    let is_file = matches!(entry.entry_type, FsEntryType::File);
+    let (bg_color, text_color, font_size, corner_rad) = if is_file {
+        (
+            &theme.file_bg_color,
+            &theme.file_text_color,
+            theme.file_font_size,
+            theme.file_bg_corner_rad,
+        )
+    } else {
+        (
+            &theme.folder_bg_color,
+            &theme.folder_text_color,
+            theme.folder_font_size,
+            theme.folder_bg_corner_rad,
+        )
+    };
+    let entry_heat = heat[node.index];
+    let (bg_color, text_color) = label_colors(
+        entry,
+        entry_heat.color.unwrap_or(bg_color),
+        text_color,
+        theme,
+    );
+    let border_color = if is_file {
+        theme
+            .bg_color
+            .as_ref()
+            .unwrap_or(&theme.hierarchy_line_color)
+    } else {
+        &theme.hierarchy_line_color
+    };
+
+    let mut group = Group::new()
+        .set("class", if is_file { "tile" } else { "tile container" })
+        .add(Title::new(node.tooltip(area_by)))
+        .add(
+            Rectangle::new()
+                .set("x", tile.x)
+                .set("y", tile.y)
+                .set("width", tile.width)
+                .set("height", tile.height)
+                .set("rx", corner_rad)
+                .set("ry", corner_rad)
+                .set("fill", bg_color.clone())
+                .set("stroke", border_color.clone())
+                .set("stroke-width", 1),
+        );
+
+    // Labels that don't fit are left out, the tooltip still names the entry
+    let fits = text_width(font, font_size, &entry.name) as f64 + LABEL_PADDING * 2.0 <= tile.width
+        && HEADER_HEIGHT <= tile.height;
+    if fits {
+        group = group.add(
+            Text::new(entry.name.clone())
+                .set("x", tile.x + LABEL_PADDING)
+                .set("y", tile.y + (HEADER_HEIGHT + font_size as f64 * 0.7) / 2.0)
+                .set("font-family", theme.font.clone())
+                .set("font-size", font_size)
+                .set("fill", text_color.clone()),
+        );
+    }
+
+    let inner = Rect {
+        x: tile.x + FOLDER_PADDING,
+        y: tile.y + HEADER_HEIGHT,
+        width: tile.width - FOLDER_PADDING * 2.0,
+        height: tile.height - HEADER_HEIGHT - FOLDER_PADDING,
+    };
+    if !node.children.is_empty() && inner.width > 1.0 && inner.height > 1.0 {
+        group = compose_tiles(group, &node.children, inner, heat, area_by, theme, font);
+    }
+
+    with_highlight_opacity(group, entry_heat.highlight(entry), theme)
+}
+
+/// Splits `rect` into rectangles with areas proportional to `weights`, which are sorted from
+/// largest to smallest. Following the squarified treemap algorithm of Bruls et al., entries are
+/// placed in rows along the shorter side, and a row grows as long as that keeps its rectangles
+/// closer to squares.
+fn squarify(weights: &[f64], rect: Rect) -> Vec<Rect> {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    let scale = rect.width * rect.height / total;
+    let areas: Vec<f64> = weights.iter().map(|w| w * scale).collect();
+
+    let mut rects = Vec::with_capacity(areas.len());
+    let mut remaining = rect;
+    let mut start = 0;
+    while start < areas.len() {
+        let side = remaining.width.min(remaining.height);
+        let mut end = start + 1;
+        while end < areas.len()
+            && worst_ratio(&areas[start..=end], side) <= worst_ratio(&areas[start..end], side)
+        {
+            end += 1;
+        }
+
+        let row_area: f64 = areas[start..end].iter().sum();
+        if remaining.width >= remaining.height {
+            // A column at the left
+            let width = row_area / remaining.height;
+            let mut y = remaining.y;
+            for area in &areas[start..end] {
+                let height = area / width;
+                rects.push(Rect {
+                    x: remaining.x,
+                    y,
+                    width,
+                    height,
+                });
+                y += height;
+            }
+            remaining.x += width;
+            remaining.width -= width;
+        } else {
+            // A row at the top
+            let height = row_area / remaining.width;
+            let mut x = remaining.x;
+            for area in &areas[start..end] {
+                let width = area / height;
+                rects.push(Rect {
+                    x,
+                    y: remaining.y,
+                    width,
+                    height,
+                });
+                x += width;
+            }
+            remaining.y += height;
+            remaining.height -= height;
+        }
+        start = end;
+    }
+    rects
+}
+
+/// The aspect ratio of the most elongated rectangle when `areas` form a row along `side`
+fn worst_ratio(areas: &[f64], side: f64) -> f64 {
+    let sum: f64 = areas.iter().sum();
+    let max = areas.iter().copied().fold(0.0, f64::max);
+    let min = areas.iter().copied().fold(f64::INFINITY, f64::min);
+    let (side_squared, sum_squared) = (side * side, sum * sum);
+    (side_squared * max / sum_squared).max(sum_squared / (side_squared * min))
+}
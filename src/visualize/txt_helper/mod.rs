@@ -0,0 +1,175 @@
+use crate::fs_parser::fs_structs::{DiffStatus, FlatFsEntry, FsEntryType, GitStatus};
+use crate::visualize::columns::metric_columns;
+use crate::visualize::wrap::Page;
+
+const ANNOTATION_GAP: usize = 2;
+const METRIC_GAP: usize = 2;
+
+/// Compose a `tree`-like plain text representation of the folder structure. With a `page`,
+/// only its rows are shown, below the folders they continue and above the page number.
+pub fn compose_txt_from_filestruct(
+    filestructure: &[FlatFsEntry],
+    show_sizes: bool,
+    page: Option<&Page>,
+) -> String {
+    // Each shown entry, and whether it is a breadcrumb continued from the previous page
+    let shown: Vec<(usize, bool)> = match page {
+        Some(page) => {
+            let segment = &page.segment;
+            segment
+                .breadcrumbs
+                .iter()
+                .map(|&i| (i, true))
+                .chain(segment.rows.clone().map(|i| (i, false)))
+                .collect()
+        }
+        None => (0..filestructure.len()).map(|i| (i, false)).collect(),
+    };
+    let deepest_breadcrumb = shown.iter().rev().find(|(_, b)| *b).map(|(i, _)| *i);
+    // Left out ancestors are marked with an ellipsis before the first breadcrumb
+    let elided_breadcrumb = page
+        .filter(|page| page.segment.elided)
+        .and_then(|page| page.segment.breadcrumbs.first().copied());
+
+    let mut lines: Vec<String> = shown
+        .iter()
+        .map(|&(i, is_breadcrumb)| {
+            let entry = &filestructure[i];
+            let mut line = tree_prefix(filestructure, i);
+            line.push_str(match entry.diff {
+                Some(DiffStatus::Added) => "+ ",
+                Some(DiffStatus::Removed) => "- ",
+                Some(DiffStatus::Modified) => "~ ",
+                Some(DiffStatus::Renamed(_)) => "» ",
+                Some(DiffStatus::Unchanged) | None => "",
+            });
+            if is_breadcrumb && Some(i) == elided_breadcrumb {
+                line.push_str("…/");
+            }
+            line.push_str(&entry.name);
+            if matches!(entry.entry_type, FsEntryType::Folder) && entry.hides_children {
+                line.push_str("/…");
+            }
+            match (&entry.link_target, entry.entry_type) {
+                (Some(commit), FsEntryType::Submodule) => line.push_str(&format!(" @ {}", commit)),
+                (Some(target), _) => line.push_str(&format!(" -> {}", target)),
+                (None, _) => {}
+            }
+            if let Some(DiffStatus::Renamed(from)) = &entry.diff {
+                line.push_str(&format!(" <- {}", from.display()));
+            }
+            line.push_str(match entry.git_status {
+                Some(GitStatus::Untracked) => " [?]",
+                Some(GitStatus::Staged) => " [S]",
+                Some(GitStatus::Modified) => " [M]",
+                Some(GitStatus::Conflicted) => " [U]",
+                None => "",
+            });
+            if let Some(ratio) = entry.coverage.and_then(|c| c.ratio()) {
+                line.push_str(&format!(" [{:.0}%]", ratio * 100.0));
+            }
+            if let Some(diagnostics) = entry.diagnostics {
+                let counts = [
+                    (diagnostics.errors, "error"),
+                    (diagnostics.warnings, "warning"),
+                    (diagnostics.notes, "note"),
+                ]
+                .iter()
+                .filter(|(count, _)| *count > 0)
+                .map(|(count, severity)| match count {
+                    1 => format!("1 {}", severity),
+                    _ => format!("{} {}s", count, severity),
+                })
+                .collect::<Vec<_>>();
+                if !counts.is_empty() {
+                    line.push_str(&format!(" [{}]", counts.join(", ")));
+                }
+            }
+            match &entry.owners {
+                Some(owners) if owners.is_empty() => line.push_str(" [unowned]"),
+                Some(owners) => line.push_str(&format!(" [{}]", owners.join(" "))),
+                None => {}
+            }
+            if is_breadcrumb && Some(i) == deepest_breadcrumb {
+                line.push_str(" (continued)");
+            }
+            line
+        })
+        .collect();
+
+    // Metrics are right-aligned in columns after the longest line, bare numbers followed by
+    // their unit
+    let columns = metric_columns(filestructure, show_sizes);
+    if !columns.is_empty() {
+        let labels_end = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        for (line, &(i, is_breadcrumb)) in lines.iter_mut().zip(&shown) {
+            line.push_str(&" ".repeat(labels_end - line.chars().count()));
+            for column in &columns {
+                let width = column
+                    .values
+                    .iter()
+                    .flatten()
+                    .map(String::len)
+                    .max()
+                    .unwrap_or(0);
+                let unit = column
+                    .unit
+                    .map(|unit| format!(" {}", unit))
+                    .unwrap_or_default();
+                match column.values[i].as_ref().filter(|_| !is_breadcrumb) {
+                    Some(value) => line.push_str(&format!(
+                        "{}{:>width$}{}",
+                        " ".repeat(METRIC_GAP),
+                        value,
+                        unit
+                    )),
+                    None => line.push_str(&" ".repeat(METRIC_GAP + width + unit.len())),
+                }
+            }
+            line.truncate(line.trim_end().len());
+        }
+    }
+
+    // Annotations are aligned in a column after the longest line
+    let column = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) + ANNOTATION_GAP;
+
+    let mut output = String::new();
+    for (line, &(i, is_breadcrumb)) in lines.iter().zip(&shown) {
+        output.push_str(line);
+        if let Some(annotation) = filestructure[i]
+            .annotation
+            .as_ref()
+            .filter(|_| !is_breadcrumb)
+        {
+            let padding = column - line.chars().count();
+            output.push_str(&format!("{}# {}", " ".repeat(padding), annotation));
+        }
+        output.push('\n');
+    }
+    if let Some(page) = page {
+        output.push_str(&format!("\nPage {} of {}\n", page.number, page.count));
+    }
+    output
+}
+
+/// Build the box-drawing prefix for the entry at `index`
+fn tree_prefix(filestructure: &[FlatFsEntry], index: usize) -> String {
+    let depth = filestructure[index].depth;
+    let mut prefix = String::new();
+    for d in 1..=depth {
+        // The ancestor (or the entry itself) at depth `d` is the last child if no sibling
+        // at the same depth follows before the subtree ends.
+        let is_last = !filestructure[index + 1..]
+            .iter()
+            .take_while(|e| e.depth >= d)
+            .any(|e| e.depth == d);
+
+        prefix.push_str(match (d == depth, is_last) {
+            (true, true) => "└── ",
+            (true, false) => "├── ",
+            (false, true) => "    ",
+            (false, false) => "│   ",
+        });
+    }
+    prefix
+}
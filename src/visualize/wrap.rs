@@ -0,0 +1,231 @@
+use crate::fs_parser::fs_structs::FlatFsEntry;
+use std::ops::Range;
+
+/// A run of rows shown together, e.g. in one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub rows: Range<usize>,
+    /// Ancestors of the first row, repeated above it when the segment continues a folder
+    pub breadcrumbs: Vec<usize>,
+    /// Whether the shallowest ancestors were left out of the breadcrumbs to make room for rows
+    pub elided: bool,
+}
+
+/// One of the pages the rows are split into
+#[derive(Debug, Clone)]
+pub struct Page {
+    /// Number of the page, starting at 1
+    pub number: usize,
+    pub count: usize,
+    pub segment: Segment,
+}
+
+/// Splits the rows into pages of at most `page_rows` rows, counting the breadcrumbs of a page.
+/// There is always at least one page, even without rows.
+pub fn paginate(filestructure: &[FlatFsEntry], page_rows: u32) -> Vec<Page> {
+    let mut segments = split_rows(filestructure, page_rows as usize);
+    if segments.is_empty() {
+        segments.push(Segment {
+            rows: 0..0,
+            breadcrumbs: Vec::new(),
+            elided: false,
+        });
+    }
+    let count = segments.len();
+    segments
+        .into_iter()
+        .enumerate()
+        .map(|(i, segment)| Page {
+            number: i + 1,
+            count,
+            segment,
+        })
+        .collect()
+}
+
+/// Splits the rows into `columns` segments of about equal length, or into segments of at most
+/// `max_rows` rows including their breadcrumbs. Without either, all rows form one segment.
+pub fn wrap_rows(
+    filestructure: &[FlatFsEntry],
+    columns: Option<u32>,
+    max_rows: Option<u32>,
+) -> Vec<Segment> {
+    match (columns, max_rows) {
+        (Some(columns), _) => split_into_columns(filestructure, columns as usize),
+        (None, Some(max_rows)) => split_rows(filestructure, max_rows as usize),
+        (None, None) => vec![Segment {
+            rows: 0..filestructure.len(),
+            breadcrumbs: Vec::new(),
+            elided: false,
+        }],
+    }
+}
+
+/// Splits the rows into at most `columns` segments, as short as that allows
+fn split_into_columns(filestructure: &[FlatFsEntry], columns: usize) -> Vec<Segment> {
+    let columns = columns.max(1);
+    let mut max_rows = filestructure.len().div_ceil(columns).max(1);
+    loop {
+        let segments = split_rows(filestructure, max_rows);
+        // Breadcrumbs and breaks at folder boundaries can take up more columns than asked for
+        if segments.len() <= columns || max_rows >= filestructure.len() {
+            return segments;
+        }
+        max_rows += 1;
+    }
+}
+
+/// Splits the rows into segments of at most `max_rows` rows, counting the breadcrumbs of a
+/// segment. Segments prefer to end before a shallow entry, ideally a top level one, as long
+/// as they stay at least half full. Breaking before the first child of a folder doesn't close
+/// anything, so segments only end there when they are full.
+fn split_rows(filestructure: &[FlatFsEntry], max_rows: usize) -> Vec<Segment> {
+    let max_rows = max_rows.max(1);
+    let mut segments = Vec::new();
+    let mut start = 0;
+    while start < filestructure.len() {
+        let mut breadcrumbs = if start == 0 {
+            Vec::new()
+        } else {
+            ancestors(filestructure, start)
+        };
+        // Every segment shows at least one row, deep ones only the deepest breadcrumbs
+        let elided = breadcrumbs.len() >= max_rows;
+        if elided {
+            breadcrumbs.drain(..=breadcrumbs.len() - max_rows);
+        }
+        let capacity = max_rows - breadcrumbs.len();
+        let mut end = (start + capacity).min(filestructure.len());
+        if end < filestructure.len() {
+            let earliest = start + (capacity / 2).max(1);
+            // The shallowest break wins, the latest one among equally shallow breaks
+            end = (earliest..=end)
+                .rev()
+                .filter(|&row| {
+                    row == end || filestructure[row].depth <= filestructure[row - 1].depth
+                })
+                .min_by_key(|&row| filestructure[row].depth)
+                .unwrap_or(end);
+        }
+        segments.push(Segment {
+            rows: start..end,
+            breadcrumbs,
+            elided,
+        });
+        start = end;
+    }
+    segments
+}
+
+/// Indices of the ancestors of a row, from the top level down
+fn ancestors(filestructure: &[FlatFsEntry], row: usize) -> Vec<usize> {
+    let mut ancestors = Vec::new();
+    let mut depth = filestructure[row].depth;
+    for i in (0..row).rev() {
+        if depth == 0 {
+            break;
+        }
+        if filestructure[i].depth < depth {
+            ancestors.push(i);
+            depth = filestructure[i].depth;
+        }
+    }
+    ancestors.reverse();
+    ancestors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Entries at the given depths, named by their position
+    fn entries(depths: &[u32]) -> Vec<FlatFsEntry> {
+        depths
+            .iter()
+            .enumerate()
+            .map(|(i, &depth)| FlatFsEntry {
+                name: i.to_string(),
+                depth,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn rows_and_breadcrumbs(segments: &[Segment]) -> Vec<(Range<usize>, Vec<usize>)> {
+        segments
+            .iter()
+            .map(|s| (s.rows.clone(), s.breadcrumbs.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn segments_end_before_shallow_entries() {
+        let filestructure = entries(&[0, 1, 1, 0, 1, 1, 1, 0]);
+        let segments = split_rows(&filestructure, 4);
+        assert_eq!(
+            rows_and_breadcrumbs(&segments),
+            [(0..3, vec![]), (3..7, vec![]), (7..8, vec![])]
+        );
+    }
+
+    #[test]
+    fn continued_folders_repeat_their_ancestors() {
+        let filestructure = entries(&[0, 1, 2, 2, 2, 2]);
+        let segments = split_rows(&filestructure, 4);
+        assert_eq!(
+            rows_and_breadcrumbs(&segments),
+            [(0..4, vec![]), (4..6, vec![0, 1])]
+        );
+        assert!(!segments[1].elided);
+    }
+
+    #[test]
+    fn deep_chains_keep_pages_full_and_within_the_limit() {
+        let filestructure = entries(&[0, 1, 2, 3, 4]);
+        let pages = paginate(&filestructure, 2);
+        let shown: Vec<(Range<usize>, Vec<usize>, bool)> = pages
+            .iter()
+            .map(|p| {
+                let s = &p.segment;
+                (s.rows.clone(), s.breadcrumbs.clone(), s.elided)
+            })
+            .collect();
+        assert_eq!(
+            shown,
+            [
+                (0..2, vec![], false),
+                (2..3, vec![1], true),
+                (3..4, vec![2], true),
+                (4..5, vec![3], true),
+            ]
+        );
+        assert!(pages.iter().all(|p| p.count == 4));
+    }
+
+    #[test]
+    fn single_row_pages_leave_out_breadcrumbs() {
+        let filestructure = entries(&[0, 1, 2]);
+        let segments = split_rows(&filestructure, 1);
+        assert_eq!(
+            rows_and_breadcrumbs(&segments),
+            [(0..1, vec![]), (1..2, vec![]), (2..3, vec![])]
+        );
+    }
+
+    #[test]
+    fn no_rows_still_make_a_page() {
+        let pages = paginate(&[], 10);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].segment.rows, 0..0);
+    }
+
+    #[test]
+    fn columns_are_balanced() {
+        let filestructure = entries(&[0, 0, 0, 0, 0, 0]);
+        let segments = wrap_rows(&filestructure, Some(3), None);
+        assert_eq!(
+            rows_and_breadcrumbs(&segments),
+            [(0..2, vec![]), (2..4, vec![]), (4..6, vec![])]
+        );
+    }
+}